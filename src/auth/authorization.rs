@@ -0,0 +1,54 @@
+use anyhow::Result;
+use std::sync::Arc;
+use casbin::{CoreApi, Enforcer, RbacApi};
+use tokio::sync::RwLock;
+
+/// Authorization layer backed by a Casbin [`Enforcer`].
+///
+/// Requests are modeled as `(subject, object, action)` triples where the
+/// subject is the caller identity resolved from the presented token, the
+/// object is a collection name (`novels`, `chapters`, `characters`, `qa`) and
+/// the action is `read` or `write`. Policy lines map subjects to the
+/// collections and actions they may touch; `g` grouping lines let operators
+/// grant whole roles (e.g. `editors`) access without redeploying.
+#[derive(Clone)]
+pub struct Authorization {
+    enforcer: Arc<RwLock<Enforcer>>,
+}
+
+impl Authorization {
+    /// Build an authorization layer from a Casbin model file and policy file.
+    pub async fn new(model_path: &str, policy_path: &str) -> Result<Self> {
+        let enforcer = Enforcer::new(model_path, policy_path).await?;
+        Ok(Self {
+            enforcer: Arc::new(RwLock::new(enforcer)),
+        })
+    }
+
+    /// Wrap an already-constructed enforcer (useful for tests / custom loaders).
+    pub fn from_enforcer(enforcer: Enforcer) -> Self {
+        Self {
+            enforcer: Arc::new(RwLock::new(enforcer)),
+        }
+    }
+
+    /// Return `true` when `subject` is permitted to perform `action` on the
+    /// `object` collection, honouring any role inheritance declared via `g`.
+    pub async fn enforce(&self, subject: &str, object: &str, action: &str) -> Result<bool> {
+        let enforcer = self.enforcer.read().await;
+        let allowed = enforcer.enforce((subject, object, action))?;
+        Ok(allowed)
+    }
+
+    /// Grant `subject` membership in `role` so it inherits the role's policies.
+    pub async fn add_role(&self, subject: &str, role: &str) -> Result<()> {
+        let mut enforcer = self.enforcer.write().await;
+        enforcer.add_role_for_user(subject, role, None).await?;
+        Ok(())
+    }
+
+    /// Access the underlying enforcer handle for advanced policy management.
+    pub fn enforcer(&self) -> Arc<RwLock<Enforcer>> {
+        self.enforcer.clone()
+    }
+}
@@ -0,0 +1,174 @@
+//! JWT bearer authentication for the REST API.
+//!
+//! [`issue_jwt`] mints an HS256 token for a subject, and [`AuthMiddleware`]
+//! validates the `Authorization: Bearer <token>` header on incoming requests,
+//! injecting an [`AuthUser`] into the request extensions. Handlers that require
+//! authentication extract [`AuthUser`] to learn the caller's id and scope; a missing or invalid
+//! token surfaces as a `401`. When [`AuthState::protect_reads`] is set, the
+//! middleware rejects unauthenticated reads too instead of passing them through.
+
+use axum::{
+    extract::{FromRequestParts, Request, State},
+    http::{header::AUTHORIZATION, request::Parts},
+    middleware::Next,
+    response::Response,
+};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+
+use crate::auth::token_store::Access;
+use crate::handlers::error::ApiError;
+
+/// Default token lifetime in seconds (one hour).
+const TOKEN_TTL_SECS: i64 = 3600;
+
+/// Claims carried by an API JWT: the subject (user id), expiry, and the
+/// provisioned token's scope, so a JWT exchanged from a read-only or
+/// novel-scoped API key carries that scope forward rather than widening it to
+/// an unscoped bearer token.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: String,
+    pub exp: i64,
+    pub access: Access,
+    #[serde(default)]
+    pub allowed_novel_ids: Vec<String>,
+}
+
+/// The authenticated caller, injected into request extensions by the middleware
+/// and extracted by handlers that require authentication.
+#[derive(Debug, Clone)]
+pub struct AuthUser {
+    pub id: String,
+    pub access: Access,
+    pub allowed_novel_ids: Vec<String>,
+}
+
+/// Mint an HS256 token for `subject`, valid for [`TOKEN_TTL_SECS`], carrying the
+/// `access` and `allowed_novel_ids` scope of the API key it was exchanged from.
+pub fn issue_jwt(
+    secret: &str,
+    subject: &str,
+    access: Access,
+    allowed_novel_ids: Vec<String>,
+) -> anyhow::Result<String> {
+    let exp = jsonwebtoken::get_current_timestamp() as i64 + TOKEN_TTL_SECS;
+    let claims = Claims {
+        sub: subject.to_string(),
+        exp,
+        access,
+        allowed_novel_ids,
+    };
+    let token = encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(secret.as_bytes()),
+    )?;
+    Ok(token)
+}
+
+/// Verify a token's signature and expiry, returning its claims.
+pub fn verify_jwt(secret: &str, token: &str) -> anyhow::Result<Claims> {
+    let data = decode::<Claims>(
+        token,
+        &DecodingKey::from_secret(secret.as_bytes()),
+        &Validation::default(),
+    )?;
+    Ok(data.claims)
+}
+
+/// State shared with [`auth_middleware`]: the signing secret and whether reads
+/// must also be authenticated.
+#[derive(Debug, Clone)]
+pub struct AuthState {
+    pub secret: String,
+    pub protect_reads: bool,
+}
+
+/// Pull the bearer token out of the `Authorization` header, if present.
+fn bearer_token(parts: &Parts) -> Option<String> {
+    parts
+        .headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(|token| token.trim().to_string())
+}
+
+/// Validate the bearer token (when present) and inject [`AuthUser`]. A missing
+/// or invalid token is rejected only when `protect_reads` is set; otherwise the
+/// request passes through unauthenticated and handler-level extractors enforce
+/// auth on the routes that need it.
+pub async fn auth_middleware(
+    State(state): State<AuthState>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let (mut parts, body) = request.into_parts();
+    match bearer_token(&parts).and_then(|token| verify_jwt(&state.secret, &token).ok()) {
+        Some(claims) => {
+            parts.extensions.insert(AuthUser {
+                id: claims.sub,
+                access: claims.access,
+                allowed_novel_ids: claims.allowed_novel_ids,
+            });
+        }
+        None if state.protect_reads => return Err(ApiError::Unauthorized),
+        None => {}
+    }
+    request = Request::from_parts(parts, body);
+    Ok(next.run(request).await)
+}
+
+#[async_trait::async_trait]
+impl<S> FromRequestParts<S> for AuthUser
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<AuthUser>()
+            .cloned()
+            .ok_or(ApiError::Unauthorized)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn issued_token_verifies_to_the_same_subject() {
+        let token = issue_jwt("secret", "alice", Access::ReadWrite, Vec::new()).unwrap();
+        let claims = verify_jwt("secret", &token).unwrap();
+        assert_eq!(claims.sub, "alice");
+    }
+
+    #[test]
+    fn issued_token_carries_the_source_key_s_scope() {
+        let token = issue_jwt(
+            "secret",
+            "alice",
+            Access::ReadOnly,
+            vec!["novel-1".to_string()],
+        )
+        .unwrap();
+        let claims = verify_jwt("secret", &token).unwrap();
+        assert_eq!(claims.access, Access::ReadOnly);
+        assert_eq!(claims.allowed_novel_ids, vec!["novel-1".to_string()]);
+    }
+
+    #[test]
+    fn verify_rejects_a_token_signed_with_a_different_secret() {
+        let token = issue_jwt("secret", "alice", Access::ReadWrite, Vec::new()).unwrap();
+        assert!(verify_jwt("a-different-secret", &token).is_err());
+    }
+
+    #[test]
+    fn verify_rejects_garbage_input() {
+        assert!(verify_jwt("secret", "not-a-jwt").is_err());
+    }
+}
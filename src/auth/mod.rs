@@ -0,0 +1,7 @@
+pub mod authorization;
+pub mod jwt;
+pub mod token_store;
+
+pub use authorization::Authorization;
+pub use jwt::{auth_middleware, issue_jwt, AuthState, AuthUser};
+pub use token_store::{Access, ApiToken, ResolvedToken, TokenStore};
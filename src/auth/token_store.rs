@@ -0,0 +1,279 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use futures::TryStreamExt;
+use mongodb::bson::{doc, oid::ObjectId, DateTime};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::db::DatabaseConnection;
+
+/// How long a resolved lookup is cached before the next presentation re-reads
+/// the `tokens` collection, trading a short staleness window (a freshly revoked
+/// token stays valid until it elapses) for avoiding a DB round-trip per request.
+const CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// The set of actions a token may perform on its allowed collections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Access {
+    ReadOnly,
+    ReadWrite,
+}
+
+impl Access {
+    pub fn allows(&self, action: &str) -> bool {
+        match self {
+            Access::ReadOnly => action == "read",
+            Access::ReadWrite => matches!(action, "read" | "write"),
+        }
+    }
+}
+
+/// A provisioned API token document. The raw token value is never stored; only
+/// its SHA-256 hash is persisted so a leaked database cannot be replayed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiToken {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub token_hash: String,
+    pub subject: String,
+    /// Human-friendly label shown in listings to identify what a token is for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    pub allowed_collections: Vec<String>,
+    /// Novels the token may act on; empty means every novel.
+    #[serde(default)]
+    pub allowed_novel_ids: Vec<String>,
+    pub access: Access,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime>,
+    #[serde(default)]
+    pub revoked: bool,
+}
+
+/// The identity and scopes a presented token resolves to.
+#[derive(Debug, Clone)]
+pub struct ResolvedToken {
+    pub subject: String,
+    pub allowed_collections: Vec<String>,
+    pub allowed_novel_ids: Vec<String>,
+    pub access: Access,
+}
+
+impl ResolvedToken {
+    /// Whether this token's scopes permit `action` on `collection`.
+    pub fn permits(&self, collection: &str, action: &str) -> bool {
+        self.access.allows(action)
+            && self.allowed_collections.iter().any(|c| c == collection)
+    }
+
+    /// Whether this token may act on `novel_id`. An empty scope grants access to
+    /// every novel.
+    pub fn permits_novel(&self, novel_id: &str) -> bool {
+        self.allowed_novel_ids.is_empty()
+            || self.allowed_novel_ids.iter().any(|n| n == novel_id)
+    }
+}
+
+/// Hash a presented token with SHA-256, matching how tokens are stored.
+pub fn hash_token(token: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(token.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Constant-time equality over two hex digests, so a near-miss hash cannot be
+/// distinguished from a total mismatch by comparison timing.
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b.iter()) {
+        diff |= x ^ y;
+    }
+    diff == 0
+}
+
+/// MongoDB-backed provider of scoped API tokens, with a short-lived in-memory
+/// cache in front of the `tokens` collection.
+#[derive(Clone)]
+pub struct TokenStore {
+    db: DatabaseConnection,
+    /// Presented-hash → (resolution, inserted-at), shared across clones so the
+    /// cache is process-wide.
+    cache: Arc<Mutex<HashMap<String, (Option<ResolvedToken>, Instant)>>>,
+}
+
+impl TokenStore {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self {
+            db,
+            cache: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Issue a new token, returning the raw value (shown to the operator once)
+    /// alongside the stored document id.
+    pub async fn issue(
+        &self,
+        subject: &str,
+        label: Option<String>,
+        allowed_collections: Vec<String>,
+        allowed_novel_ids: Vec<String>,
+        access: Access,
+        expires_at: Option<DateTime>,
+    ) -> Result<(String, ObjectId)> {
+        let raw = uuid::Uuid::new_v4().simple().to_string();
+        let token = ApiToken {
+            id: None,
+            token_hash: hash_token(&raw),
+            subject: subject.to_string(),
+            label,
+            allowed_collections,
+            allowed_novel_ids,
+            access,
+            expires_at,
+            revoked: false,
+        };
+        let collection = self.db.get_collection::<ApiToken>("tokens");
+        let result = collection.insert_one(token, None).await?;
+        let id = result.inserted_id.as_object_id().unwrap();
+        Ok((raw, id))
+    }
+
+    /// List all provisioned tokens (hashes only, never raw values).
+    pub async fn list(&self) -> Result<Vec<ApiToken>> {
+        let collection = self.db.get_collection::<ApiToken>("tokens");
+        let cursor = collection.find(doc! {}, None).await?;
+        Ok(cursor.try_collect().await?)
+    }
+
+    /// Revoke a token by id, leaving the record in place for auditing. The
+    /// lookup cache is cleared so the revocation takes effect immediately rather
+    /// than after [`CACHE_TTL`].
+    pub async fn revoke(&self, id: &ObjectId) -> Result<bool> {
+        let collection = self.db.get_collection::<ApiToken>("tokens");
+        let result = collection
+            .update_one(doc! { "_id": id }, doc! { "$set": { "revoked": true } }, None)
+            .await?;
+        self.cache.lock().unwrap().clear();
+        Ok(result.modified_count > 0)
+    }
+
+    /// Resolve a presented token to its subject and scopes, rejecting revoked or
+    /// expired tokens. Recent lookups are served from an in-memory cache with a
+    /// [`CACHE_TTL`] lifetime to avoid a DB round-trip per request.
+    pub async fn resolve(&self, presented: &str) -> Result<Option<ResolvedToken>> {
+        let hash = hash_token(presented);
+        if let Some(cached) = self.cached(&hash) {
+            return Ok(cached);
+        }
+
+        let collection = self.db.get_collection::<ApiToken>("tokens");
+        let token = collection
+            .find_one(doc! { "token_hash": &hash, "revoked": false }, None)
+            .await?;
+
+        let resolved = token.and_then(|token| {
+            // Defence in depth: the index lookup already matched the hash, but
+            // compare again in constant time before trusting the record.
+            if !constant_time_eq(&token.token_hash, &hash) {
+                return None;
+            }
+            if let Some(expiry) = token.expires_at {
+                if expiry < DateTime::now() {
+                    return None;
+                }
+            }
+            Some(ResolvedToken {
+                subject: token.subject,
+                allowed_collections: token.allowed_collections,
+                allowed_novel_ids: token.allowed_novel_ids,
+                access: token.access,
+            })
+        });
+
+        self.cache
+            .lock()
+            .unwrap()
+            .insert(hash, (resolved.clone(), Instant::now()));
+        Ok(resolved)
+    }
+
+    /// Look up a still-fresh cache entry for `hash`, if any.
+    fn cached(&self, hash: &str) -> Option<Option<ResolvedToken>> {
+        let cache = self.cache.lock().unwrap();
+        cache.get(hash).and_then(|(resolved, inserted)| {
+            if inserted.elapsed() < CACHE_TTL {
+                Some(resolved.clone())
+            } else {
+                None
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_only_access_allows_read_but_not_write() {
+        assert!(Access::ReadOnly.allows("read"));
+        assert!(!Access::ReadOnly.allows("write"));
+    }
+
+    #[test]
+    fn read_write_access_allows_both_actions() {
+        assert!(Access::ReadWrite.allows("read"));
+        assert!(Access::ReadWrite.allows("write"));
+    }
+
+    #[test]
+    fn constant_time_eq_matches_identical_hashes() {
+        let hash = hash_token("some-secret");
+        assert!(constant_time_eq(&hash, &hash));
+    }
+
+    #[test]
+    fn constant_time_eq_rejects_mismatched_hashes() {
+        assert!(!constant_time_eq(&hash_token("a"), &hash_token("b")));
+        assert!(!constant_time_eq("short", "much-longer-digest"));
+    }
+
+    #[test]
+    fn permits_respects_access_and_collection_scope() {
+        let resolved = ResolvedToken {
+            subject: "alice".to_string(),
+            allowed_collections: vec!["chapters".to_string()],
+            allowed_novel_ids: Vec::new(),
+            access: Access::ReadOnly,
+        };
+        assert!(resolved.permits("chapters", "read"));
+        assert!(!resolved.permits("chapters", "write"));
+        assert!(!resolved.permits("novels", "read"));
+    }
+
+    #[test]
+    fn permits_novel_treats_empty_scope_as_unrestricted() {
+        let unrestricted = ResolvedToken {
+            subject: "alice".to_string(),
+            allowed_collections: Vec::new(),
+            allowed_novel_ids: Vec::new(),
+            access: Access::ReadOnly,
+        };
+        assert!(unrestricted.permits_novel("any-novel-id"));
+
+        let scoped = ResolvedToken {
+            allowed_novel_ids: vec!["novel-1".to_string()],
+            ..unrestricted
+        };
+        assert!(scoped.permits_novel("novel-1"));
+        assert!(!scoped.permits_novel("novel-2"));
+    }
+}
@@ -0,0 +1,247 @@
+//! `argh`-based management command surface. `serve` (the default) runs the
+//! servers; the remaining subcommands drive the existing services directly so
+//! operators and CI can script setup and verification without issuing HTTP
+//! calls.
+
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use argh::FromArgs;
+use mongodb::bson::{doc, oid::ObjectId, DateTime, Document};
+
+use crate::auth::{Access, Authorization, TokenStore};
+use crate::config::Config;
+use crate::mcp::server::MCPDatabaseServer;
+use crate::services::db_service::MongoDBService;
+
+/// MongoDB novel MCP server and management CLI.
+#[derive(FromArgs)]
+pub struct Cli {
+    /// path to a TOML config file (overrides CONFIG_PATH)
+    #[argh(option)]
+    pub config: Option<String>,
+
+    #[argh(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum Command {
+    Serve(ServeCmd),
+    Query(QueryCmd),
+    Ls(LsCmd),
+    Token(TokenCmd),
+    Migrate(MigrateCmd),
+}
+
+/// Run the SSE and CRUD/admin servers (default).
+#[derive(FromArgs)]
+#[argh(subcommand, name = "serve")]
+pub struct ServeCmd {
+    /// serve the MCP protocol over stdio (newline-delimited JSON-RPC) instead
+    /// of binding the SSE/CRUD listeners
+    #[argh(switch)]
+    pub stdio: bool,
+}
+
+/// Run the natural-language query pipeline locally and print the formatted output.
+#[derive(FromArgs)]
+#[argh(subcommand, name = "query")]
+pub struct QueryCmd {
+    /// natural-language query text
+    #[argh(positional)]
+    pub text: String,
+
+    /// API token presented to the authorization layer
+    #[argh(option, default = "String::new()")]
+    pub token: String,
+}
+
+/// Count and list entries in a collection.
+#[derive(FromArgs)]
+#[argh(subcommand, name = "ls")]
+pub struct LsCmd {
+    /// collection name (novels, chapters, characters, qa)
+    #[argh(positional)]
+    pub collection: String,
+
+    /// maximum number of entries to print
+    #[argh(option, default = "20")]
+    pub limit: i64,
+}
+
+/// Manage scoped API tokens.
+#[derive(FromArgs)]
+#[argh(subcommand, name = "token")]
+pub struct TokenCmd {
+    #[argh(subcommand)]
+    pub action: TokenAction,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum TokenAction {
+    Issue(TokenIssue),
+    List(TokenList),
+    Revoke(TokenRevoke),
+}
+
+/// Issue a new token and print the raw value once.
+#[derive(FromArgs)]
+#[argh(subcommand, name = "issue")]
+pub struct TokenIssue {
+    /// authorization subject the token resolves to
+    #[argh(option)]
+    pub subject: String,
+
+    /// human-friendly label shown in token listings
+    #[argh(option)]
+    pub label: Option<String>,
+
+    /// comma-separated collections the token may access
+    #[argh(option)]
+    pub collections: String,
+
+    /// comma-separated novel ids the token may act on (empty means all)
+    #[argh(option)]
+    pub novels: Option<String>,
+
+    /// grant read-write access instead of read-only
+    #[argh(switch)]
+    pub write: bool,
+
+    /// expire the token after this many days
+    #[argh(option)]
+    pub expires_in_days: Option<i64>,
+}
+
+/// List provisioned tokens (hashes only).
+#[derive(FromArgs)]
+#[argh(subcommand, name = "list")]
+pub struct TokenList {}
+
+/// Revoke a token by its document id.
+#[derive(FromArgs)]
+#[argh(subcommand, name = "revoke")]
+pub struct TokenRevoke {
+    /// token document id
+    #[argh(positional)]
+    pub id: String,
+}
+
+/// Run pending schema/index migrations, then exit.
+#[derive(FromArgs)]
+#[argh(subcommand, name = "migrate")]
+pub struct MigrateCmd {}
+
+/// Build the MCP server wired to the shared services, mirroring `serve`.
+async fn build_server(config: &Config) -> Result<MCPDatabaseServer<MongoDBService>> {
+    let db_service = MongoDBService::new(&config.mongodb).await?;
+    let db_connection = db_service.db_connection();
+    let authz = Authorization::new(&config.auth.model_path, &config.auth.policy_path).await?;
+    let token_store = TokenStore::new(db_connection);
+    let cache = crate::services::cache::from_config(&config.cache);
+    Ok(MCPDatabaseServer::new(db_service, authz, token_store, cache))
+}
+
+pub async fn run_query(config: &Config, cmd: QueryCmd) -> Result<()> {
+    let server = build_server(config).await?;
+    let output = server
+        .query(cmd.text, cmd.token)
+        .await
+        .map_err(|e| anyhow!(e))?;
+    println!("{}", output);
+    Ok(())
+}
+
+pub async fn run_ls(config: &Config, cmd: LsCmd) -> Result<()> {
+    let db_service = MongoDBService::new(&config.mongodb).await?;
+    let collection = db_service
+        .db_connection()
+        .get_collection::<Document>(&cmd.collection);
+
+    let count = collection.count_documents(doc! {}, None).await?;
+    println!("{}: {} document(s)", cmd.collection, count);
+
+    let mut cursor = collection
+        .find(doc! {}, mongodb::options::FindOptions::builder().limit(cmd.limit).build())
+        .await?;
+    use futures::TryStreamExt;
+    while let Some(doc) = cursor.try_next().await? {
+        let id = doc.get_object_id("_id").map(|o| o.to_hex()).unwrap_or_default();
+        let label = doc
+            .get_str("title")
+            .or_else(|_| doc.get_str("name"))
+            .or_else(|_| doc.get_str("question"))
+            .unwrap_or("<no label>");
+        println!("  {}  {}", id, label);
+    }
+    Ok(())
+}
+
+pub async fn run_token(config: &Config, cmd: TokenCmd) -> Result<()> {
+    let db_service = MongoDBService::new(&config.mongodb).await?;
+    let store = TokenStore::new(db_service.db_connection());
+
+    match cmd.action {
+        TokenAction::Issue(args) => {
+            let collections = args
+                .collections
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect();
+            let novel_ids = args
+                .novels
+                .as_deref()
+                .map(|s| {
+                    s.split(',')
+                        .map(|s| s.trim().to_string())
+                        .filter(|s| !s.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default();
+            let access = if args.write { Access::ReadWrite } else { Access::ReadOnly };
+            let expires_at = args.expires_in_days.map(|days| {
+                DateTime::from_millis(DateTime::now().timestamp_millis() + days * 86_400_000)
+            });
+            let (raw, id) = store
+                .issue(&args.subject, args.label, collections, novel_ids, access, expires_at)
+                .await?;
+            println!("Issued token {} for '{}'", id.to_hex(), args.subject);
+            println!("Token (shown once): {}", raw);
+        }
+        TokenAction::List(_) => {
+            for token in store.list().await? {
+                let id = token.id.map(|o| o.to_hex()).unwrap_or_default();
+                println!(
+                    "{}  subject={}  label={}  access={:?}  collections={:?}  novels={:?}  revoked={}",
+                    id,
+                    token.subject,
+                    token.label.as_deref().unwrap_or("-"),
+                    token.access,
+                    token.allowed_collections,
+                    token.allowed_novel_ids,
+                    token.revoked
+                );
+            }
+        }
+        TokenAction::Revoke(args) => {
+            let id = ObjectId::parse_str(&args.id)?;
+            if store.revoke(&id).await? {
+                println!("Revoked token {}", args.id);
+            } else {
+                println!("No token matched id {}", args.id);
+            }
+        }
+    }
+    Ok(())
+}
+
+pub async fn run_migrate(config: &Config) -> Result<()> {
+    let db_service = MongoDBService::new(&config.mongodb).await?;
+    crate::db::migrator::run_pending(&db_service.db_connection()).await?;
+    println!("Migrations up to date.");
+    Ok(())
+}
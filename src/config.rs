@@ -0,0 +1,220 @@
+//! Layered configuration: built-in defaults, overridden by an optional TOML
+//! file, overridden in turn by environment variables.
+//!
+//! The file path comes from `--config <path>` or `CONFIG_PATH`; when neither is
+//! set and no file is present the defaults (plus any env overrides) are used.
+//! This replaces the scattered implicit `env::var` reads and the hardcoded
+//! `api_port = base_port + 1` derivation that lived in `main.rs`.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+/// Fully merged server configuration.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub server: ServerConfig,
+    pub mongodb: MongoConfig,
+    pub auth: AuthConfig,
+    pub cache: CacheConfig,
+    /// Whether the write-side MCP tools are permitted at runtime.
+    pub mcp_write_access: bool,
+}
+
+/// Listener ports and bind address shared by the SSE and CRUD servers.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ServerConfig {
+    pub bind_addr: String,
+    pub sse_port: u16,
+    pub api_port: u16,
+    /// Upper bound on the page size a list endpoint will honour, protecting the
+    /// database from unbounded `limit` values.
+    pub max_page_size: i64,
+    /// Maximum size in bytes of an uploaded cover image, rejected with `413`
+    /// beyond this.
+    pub max_upload_bytes: usize,
+    /// Default page size for MCP tool and listing cursor pagination, kept small
+    /// so a client targeting the advertised ~3k-token window gets compact pages.
+    pub mcp_page_size: usize,
+}
+
+/// MongoDB connection settings.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct MongoConfig {
+    pub uri: String,
+    pub database: String,
+    pub pool_max_size: usize,
+}
+
+/// Casbin model/policy file locations.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct AuthConfig {
+    pub model_path: String,
+    pub policy_path: String,
+    /// HMAC secret used to sign and verify the REST API's JWT bearer tokens.
+    pub jwt_secret: String,
+    /// When `true`, read endpoints also require a valid bearer token; otherwise
+    /// reads are public and only mutations are authenticated.
+    pub protect_reads: bool,
+}
+
+/// Formatted-result cache settings.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// Connection URL; when empty the in-memory LRU fallback is used.
+    pub redis_url: String,
+    pub ttl_secs: u64,
+    pub lru_capacity: usize,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            bind_addr: "0.0.0.0".to_string(),
+            sse_port: 3000,
+            api_port: 3001,
+            max_page_size: 100,
+            max_upload_bytes: 5 * 1024 * 1024,
+            mcp_page_size: crate::handlers::mcp_handler::DEFAULT_PAGE_SIZE,
+        }
+    }
+}
+
+impl Default for MongoConfig {
+    fn default() -> Self {
+        Self {
+            uri: "mongodb://localhost:27017".to_string(),
+            database: "novels".to_string(),
+            pool_max_size: 16,
+        }
+    }
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            model_path: "config/rbac_model.conf".to_string(),
+            policy_path: "config/rbac_policy.csv".to_string(),
+            jwt_secret: "dev-insecure-jwt-secret".to_string(),
+            protect_reads: false,
+        }
+    }
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            redis_url: String::new(),
+            ttl_secs: 300,
+            lru_capacity: 1024,
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            server: ServerConfig::default(),
+            mongodb: MongoConfig::default(),
+            auth: AuthConfig::default(),
+            cache: CacheConfig::default(),
+            mcp_write_access: cfg!(feature = "mcp_write_access"),
+        }
+    }
+}
+
+impl Config {
+    /// Build the merged config: defaults ⟵ TOML file ⟵ environment.
+    ///
+    /// `path` is the explicit `--config` value; when `None` the `CONFIG_PATH`
+    /// environment variable is consulted. When neither names a file, defaults
+    /// (plus env overrides) apply; a named file that cannot be read or parsed
+    /// is an error.
+    pub fn load(path: Option<&str>) -> Result<Self> {
+        let path = path
+            .map(str::to_string)
+            .or_else(|| std::env::var("CONFIG_PATH").ok());
+
+        let mut config = match path {
+            Some(path) => {
+                let text = std::fs::read_to_string(&path)
+                    .with_context(|| format!("reading config file {}", path))?;
+                toml::from_str(&text).with_context(|| format!("parsing config file {}", path))?
+            }
+            None => Config::default(),
+        };
+
+        config.apply_env_overrides();
+        Ok(config)
+    }
+
+    /// Apply environment-variable overrides on top of whatever the file (or the
+    /// defaults) provided. Each variable mirrors the historical implicit reads.
+    fn apply_env_overrides(&mut self) {
+        if let Ok(v) = std::env::var("PORT") {
+            if let Ok(port) = v.parse::<u16>() {
+                self.server.sse_port = port;
+                // Preserve the legacy +1 convention unless API_PORT is set.
+                self.server.api_port = port + 1;
+            }
+        }
+        if let Ok(v) = std::env::var("API_PORT") {
+            if let Ok(port) = v.parse() {
+                self.server.api_port = port;
+            }
+        }
+        if let Ok(v) = std::env::var("BIND_ADDR") {
+            self.server.bind_addr = v;
+        }
+        if let Ok(v) = std::env::var("MAX_PAGE_SIZE") {
+            if let Ok(n) = v.parse() {
+                self.server.max_page_size = n;
+            }
+        }
+        if let Ok(v) = std::env::var("MAX_UPLOAD_BYTES") {
+            if let Ok(n) = v.parse() {
+                self.server.max_upload_bytes = n;
+            }
+        }
+        if let Ok(v) = std::env::var("MCP_PAGE_SIZE") {
+            if let Ok(n) = v.parse() {
+                self.server.mcp_page_size = n;
+            }
+        }
+        if let Ok(v) = std::env::var("MONGODB_URI") {
+            self.mongodb.uri = v;
+        }
+        if let Ok(v) = std::env::var("DATABASE_NAME") {
+            self.mongodb.database = v;
+        }
+        if let Ok(v) = std::env::var("MONGODB_POOL_MAX_SIZE") {
+            if let Ok(n) = v.parse() {
+                self.mongodb.pool_max_size = n;
+            }
+        }
+        if let Ok(v) = std::env::var("CASBIN_MODEL_PATH") {
+            self.auth.model_path = v;
+        }
+        if let Ok(v) = std::env::var("CASBIN_POLICY_PATH") {
+            self.auth.policy_path = v;
+        }
+        if let Ok(v) = std::env::var("JWT_SECRET") {
+            self.auth.jwt_secret = v;
+        }
+        if let Ok(v) = std::env::var("PROTECT_READS") {
+            self.auth.protect_reads = matches!(v.as_str(), "1" | "true" | "yes");
+        }
+        if let Ok(v) = std::env::var("REDIS_URL") {
+            self.cache.redis_url = v;
+        }
+        if let Ok(v) = std::env::var("CACHE_TTL_SECS") {
+            if let Ok(n) = v.parse() {
+                self.cache.ttl_secs = n;
+            }
+        }
+    }
+}
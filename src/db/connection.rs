@@ -3,19 +3,32 @@ use mongodb::{
     options::{ClientOptions, ResolverConfig},
     Client, Collection, Database,
 };
-use std::env;
+use crate::db::pool::{MongoManager, MongoPool};
 
+/// `DatabaseConnection` is intentionally a thin, concrete wrapper around a
+/// MongoDB [`Client`]/[`Database`], not an implementation of a swappable
+/// `Storage` trait. The backend-agnostic seam already lives one layer up, in
+/// [`DatabaseService`](crate::services::db_service::DatabaseService) and
+/// [`CrudService`](crate::services::crud_service::CrudService) — both are
+/// generic traits the MCP and REST handlers are written against, and both can
+/// already be faked for tests without touching Mongo. A narrower `Storage`
+/// trait underneath this struct (tried and reverted; see
+/// `SFBB/mcp-mongodb-novel-server#chunk6-2`) would only have covered a handful
+/// of the 20+ operations those traits already expose, leaving the rest
+/// hardwired to Mongo regardless — strictly worse than the one seam this repo
+/// already has. Not doing it.
 #[derive(Clone)]
 pub struct DatabaseConnection {
     client: Client,
     db: Database,
+    pool: MongoPool,
+    db_name: String,
 }
 
 impl DatabaseConnection {
-    pub async fn new() -> Result<Self> {
-        // Load the MongoDB connection string from an environment variable
-        let uri = env::var("MONGODB_URI").expect("MONGODB_URI must be set");
-        let db_name = env::var("DATABASE_NAME").expect("DATABASE_NAME must be set");
+    pub async fn new(uri: &str, db_name: &str, pool_max_size: usize) -> Result<Self> {
+        let uri = uri.to_string();
+        let db_name = db_name.to_string();
 
         // Create a ClientOptions instance and set the resolver config
         let options = ClientOptions::parse_with_resolver_config(&uri, ResolverConfig::cloudflare())
@@ -25,6 +38,9 @@ impl DatabaseConnection {
         let client = Client::with_options(options)?;
         let db = client.database(&db_name);
 
+        // Build a deadpool-managed pool of clients for concurrent traffic
+        let pool = MongoManager::pool(uri, pool_max_size)?;
+
         // Test the connection with a valid ping command
         client
             .database("admin")
@@ -33,10 +49,29 @@ impl DatabaseConnection {
 
         tracing::info!("Connected to MongoDB");
 
-        Ok(Self { client, db })
+        Ok(Self {
+            client,
+            db,
+            pool,
+            db_name,
+        })
     }
 
     pub fn get_collection<T>(&self, collection_name: &str) -> Collection<T> {
         self.db.collection(collection_name)
     }
+
+    /// Borrow the primary [`Database`] handle (used by the migration runner).
+    pub fn database(&self) -> Database {
+        self.db.clone()
+    }
+
+    /// Check out a pooled client and return the target database on it. The
+    /// pooled object goes back to the pool when this function returns; the
+    /// returned [`Database`] holds its own clone of the client and does not
+    /// count against the pool's `max_size`.
+    pub async fn checkout(&self) -> Result<Database> {
+        let client = self.pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        Ok(client.database(&self.db_name))
+    }
 }
\ No newline at end of file
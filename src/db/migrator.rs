@@ -0,0 +1,123 @@
+use anyhow::Result;
+use mongodb::{
+    bson::doc,
+    options::IndexOptions,
+    Database, IndexModel,
+};
+
+use crate::db::DatabaseConnection;
+
+/// A single, ordered schema/index step. Steps are identified by a monotonically
+/// increasing `version`; applied versions are recorded in `_migrations` so a
+/// re-run only executes the pending tail.
+pub struct Migration {
+    pub version: u32,
+    pub description: &'static str,
+    pub run: fn(&Database) -> futures::future::BoxFuture<'_, Result<()>>,
+}
+
+/// Record written to `_migrations` once a step succeeds.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct MigrationRecord {
+    version: u32,
+    description: String,
+}
+
+/// Ordered list of migrations the server knows about.
+fn migrations() -> Vec<Migration> {
+    vec![
+        Migration {
+            version: 1,
+            description: "create core collections",
+            run: |db| Box::pin(create_collections(db.clone())),
+        },
+        Migration {
+            version: 2,
+            description: "build text/regex indexes",
+            run: |db| Box::pin(build_indexes(db.clone())),
+        },
+        Migration {
+            version: 3,
+            description: "index character name/description for full-text search",
+            run: |db| Box::pin(build_character_text_index(db.clone())),
+        },
+    ]
+}
+
+async fn create_collections(db: Database) -> Result<()> {
+    for name in ["novels", "chapters", "characters", "qa"] {
+        // `create_collection` is idempotent once guarded by the version check,
+        // but tolerate a pre-existing collection for safety.
+        let _ = db.create_collection(name, None).await;
+    }
+    Ok(())
+}
+
+async fn build_indexes(db: Database) -> Result<()> {
+    let text = |field: &str| {
+        IndexModel::builder()
+            .keys(doc! { field: "text" })
+            .options(IndexOptions::builder().build())
+            .build()
+    };
+
+    db.collection::<mongodb::bson::Document>("novels")
+        .create_index(text("title"), None)
+        .await?;
+    db.collection::<mongodb::bson::Document>("chapters")
+        .create_index(text("summary"), None)
+        .await?;
+    db.collection::<mongodb::bson::Document>("qa")
+        .create_index(text("question"), None)
+        .await?;
+    Ok(())
+}
+
+/// Give the `characters` collection a compound text index so the fuzzy
+/// full-text endpoint can prefilter character hits via `$text`.
+async fn build_character_text_index(db: Database) -> Result<()> {
+    let index = IndexModel::builder()
+        .keys(doc! { "name": "text", "description": "text" })
+        .options(IndexOptions::builder().build())
+        .build();
+    db.collection::<mongodb::bson::Document>("characters")
+        .create_index(index, None)
+        .await?;
+    Ok(())
+}
+
+/// Run every pending migration in order, recording each applied version. Safe to
+/// call on every startup: already-applied steps are skipped.
+pub async fn run_pending(conn: &DatabaseConnection) -> Result<()> {
+    let db = conn.database();
+    let applied = conn.get_collection::<MigrationRecord>("_migrations");
+
+    for migration in migrations() {
+        let already = applied
+            .find_one(doc! { "version": migration.version }, None)
+            .await?
+            .is_some();
+        if already {
+            continue;
+        }
+
+        tracing::info!(
+            "Applying migration {}: {}",
+            migration.version,
+            migration.description
+        );
+        (migration.run)(&db).await?;
+
+        applied
+            .insert_one(
+                MigrationRecord {
+                    version: migration.version,
+                    description: migration.description.to_string(),
+                },
+                None,
+            )
+            .await?;
+    }
+
+    Ok(())
+}
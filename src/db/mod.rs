@@ -0,0 +1,6 @@
+pub mod connection;
+pub mod migrator;
+pub mod pool;
+
+pub use connection::DatabaseConnection;
+pub use pool::{MongoManager, MongoPool};
@@ -0,0 +1,55 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use deadpool::managed::{Manager, Metrics, Pool, RecycleResult};
+use mongodb::{
+    options::{ClientOptions, ResolverConfig},
+    Client,
+};
+
+/// A pooled MongoDB client handle. Each checkout hands out a cloned [`Client`]
+/// (the driver multiplexes operations over a shared connection set) but the
+/// pool bounds how many logical handles are live at once so a burst of SSE +
+/// CRUD traffic cannot exhaust the server.
+pub type MongoPool = Pool<MongoManager>;
+
+/// deadpool [`Manager`] that mints and recycles MongoDB clients.
+pub struct MongoManager {
+    uri: String,
+}
+
+impl MongoManager {
+    pub fn new(uri: impl Into<String>) -> Self {
+        Self { uri: uri.into() }
+    }
+
+    /// Build a pool bounded at `max_size` logical handles.
+    pub fn pool(uri: impl Into<String>, max_size: usize) -> Result<MongoPool> {
+        let pool = Pool::builder(MongoManager::new(uri))
+            .max_size(max_size)
+            .build()?;
+        Ok(pool)
+    }
+
+}
+
+#[async_trait]
+impl Manager for MongoManager {
+    type Type = Client;
+    type Error = mongodb::error::Error;
+
+    async fn create(&self) -> Result<Client, Self::Error> {
+        let options =
+            ClientOptions::parse_with_resolver_config(&self.uri, ResolverConfig::cloudflare())
+                .await?;
+        Client::with_options(options)
+    }
+
+    async fn recycle(&self, client: &mut Client, _: &Metrics) -> RecycleResult<Self::Error> {
+        // A cheap liveness check keeps stale handles from being reused.
+        client
+            .database("admin")
+            .run_command(mongodb::bson::doc! { "ping": 1 }, None)
+            .await?;
+        Ok(())
+    }
+}
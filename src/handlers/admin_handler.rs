@@ -0,0 +1,140 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Json},
+};
+use mongodb::bson::{doc, oid::ObjectId, Document};
+use serde_json::json;
+use std::sync::Arc;
+
+use crate::db::DatabaseConnection;
+use crate::handlers::router_macros::route_table;
+use crate::services::analytics::AnalyticsService;
+use crate::utils::metrics::metrics;
+
+/// Collections whose document counts are reported by `/collections/stats`.
+const STAT_COLLECTIONS: [&str; 4] = ["novels", "chapters", "characters", "qa"];
+
+/// Readiness probe: succeeds only when MongoDB answers a `ping`.
+pub async fn health(State(db): State<Arc<DatabaseConnection>>) -> impl IntoResponse {
+    match db.database().run_command(doc! { "ping": 1 }, None).await {
+        Ok(_) => (StatusCode::OK, Json(json!({ "status": "ok" }))),
+        Err(e) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({ "status": "unavailable", "error": e.to_string() })),
+        ),
+    }
+}
+
+/// Prometheus text-format dump of the process-wide [`metrics`] registry.
+pub async fn prometheus_metrics() -> impl IntoResponse {
+    (StatusCode::OK, metrics().render_prometheus())
+}
+
+/// Per-collection document counts, useful for quick capacity checks.
+pub async fn collection_stats(State(db): State<Arc<DatabaseConnection>>) -> impl IntoResponse {
+    let mut counts = serde_json::Map::new();
+    for name in STAT_COLLECTIONS {
+        let collection = db.get_collection::<Document>(name);
+        match collection.count_documents(doc! {}, None).await {
+            Ok(count) => {
+                counts.insert(name.to_string(), json!(count));
+            }
+            Err(e) => {
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({ "error": e.to_string() })),
+                );
+            }
+        }
+    }
+    (StatusCode::OK, Json(json!({ "collections": counts })))
+}
+
+/// Operability router mounted alongside `api_router`: readiness, metrics, and
+/// collection statistics.
+pub fn admin_router(db: Arc<DatabaseConnection>) -> axum::Router {
+    route_table!(db, {
+        get "/health" => health,
+        get "/metrics" => prometheus_metrics,
+        get "/collections/stats" => collection_stats,
+    })
+}
+
+/// Render an analytics failure as a 500 with the error string, matching the
+/// response shape of the other admin handlers.
+fn analytics_error(e: anyhow::Error) -> (StatusCode, Json<serde_json::Value>) {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(json!({ "error": e.to_string() })),
+    )
+}
+
+/// Parse a path `novel_id`, rejecting a malformed id with a 400.
+fn parse_novel_id(novel_id: &str) -> Result<ObjectId, (StatusCode, Json<serde_json::Value>)> {
+    ObjectId::parse_str(novel_id).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": format!("'{}' is not a valid id", novel_id) })),
+        )
+    })
+}
+
+/// Chapters grouped under each novel, most prolific first.
+pub async fn chapters_per_novel(
+    State(analytics): State<Arc<AnalyticsService>>,
+) -> impl IntoResponse {
+    match analytics.chapters_per_novel().await {
+        Ok(rows) => (StatusCode::OK, Json(json!(rows))),
+        Err(e) => analytics_error(e),
+    }
+}
+
+/// Frequency of each tag across all novels, most common first.
+pub async fn tag_histogram(State(analytics): State<Arc<AnalyticsService>>) -> impl IntoResponse {
+    match analytics.tag_histogram().await {
+        Ok(rows) => (StatusCode::OK, Json(json!(rows))),
+        Err(e) => analytics_error(e),
+    }
+}
+
+/// Character counts per role within a single novel.
+pub async fn character_role_counts(
+    State(analytics): State<Arc<AnalyticsService>>,
+    Path(novel_id): Path<String>,
+) -> impl IntoResponse {
+    let novel_id = match parse_novel_id(&novel_id) {
+        Ok(id) => id,
+        Err(response) => return response,
+    };
+    match analytics.character_role_counts(novel_id).await {
+        Ok(rows) => (StatusCode::OK, Json(json!(rows))),
+        Err(e) => analytics_error(e),
+    }
+}
+
+/// Per-chapter word count within a novel, in chapter order.
+pub async fn word_count_over_chapters(
+    State(analytics): State<Arc<AnalyticsService>>,
+    Path(novel_id): Path<String>,
+) -> impl IntoResponse {
+    let novel_id = match parse_novel_id(&novel_id) {
+        Ok(id) => id,
+        Err(response) => return response,
+    };
+    match analytics.word_count_over_chapters(novel_id).await {
+        Ok(rows) => (StatusCode::OK, Json(json!(rows))),
+        Err(e) => analytics_error(e),
+    }
+}
+
+/// Aggregation-analytics router mounted alongside `api_router`, serving the
+/// [`AnalyticsService`] views over the novel collections.
+pub fn analytics_router(analytics: Arc<AnalyticsService>) -> axum::Router {
+    route_table!(analytics, {
+        get "/analytics/chapters-per-novel" => chapters_per_novel,
+        get "/analytics/tag-histogram" => tag_histogram,
+        get "/analytics/novels/:novel_id/character-roles" => character_role_counts,
+        get "/analytics/novels/:novel_id/word-counts" => word_count_over_chapters,
+    })
+}
@@ -0,0 +1,135 @@
+//! Cover-image upload and retrieval.
+//!
+//! `POST /api/novels/:id/cover` accepts a multipart image, decodes and
+//! re-encodes it through the `image` crate to strip anything that is not a real
+//! image, bounds its dimensions, and stores the normalized bytes via
+//! [`CoverService`]. `GET /api/novels/:id/cover` streams the stored image back
+//! with its content type. Uploads require the caller to own the parent novel.
+
+use std::io::Cursor;
+use std::sync::Arc;
+
+use axum::{
+    extract::{Multipart, Path, State},
+    http::{header::CONTENT_TYPE, StatusCode},
+    response::{IntoResponse, Json, Response},
+    routing::get,
+    Router,
+};
+use image::{ImageFormat, ImageOutputFormat};
+use mongodb::bson::oid::ObjectId;
+use serde_json::json;
+
+use crate::auth::AuthUser;
+use crate::handlers::crud_handler::authorize_owner;
+use crate::handlers::error::ApiError;
+use crate::services::cover_service::CoverService;
+use crate::services::crud_service::CrudService;
+use crate::services::NovelCrudService;
+
+/// Longest edge, in pixels, a stored cover is scaled down to.
+const MAX_DIMENSION: u32 = 1024;
+
+/// State shared by the cover endpoints: the cover store, the novel service used
+/// for ownership checks, and the upload size ceiling.
+#[derive(Clone)]
+pub struct CoverState {
+    pub covers: Arc<CoverService>,
+    pub novels: Arc<NovelCrudService>,
+    pub max_upload_bytes: usize,
+}
+
+/// Decode `bytes` as an image, bound its dimensions, and re-encode to a
+/// normalized format — PNG when a PNG, GIF or WebP source carries alpha, JPEG
+/// otherwise.
+/// Returns the encoded bytes and the MIME type to serve them with. A payload
+/// that does not decode as an image is rejected as unsupported media.
+fn normalize_image(bytes: &[u8]) -> Result<(Vec<u8>, &'static str), ApiError> {
+    let format = image::guess_format(bytes)
+        .map_err(|_| ApiError::UnsupportedMediaType("uploaded file is not a recognized image".to_string()))?;
+    let image = image::load_from_memory(bytes)
+        .map_err(|_| ApiError::UnsupportedMediaType("uploaded file could not be decoded as an image".to_string()))?;
+
+    // Scale down to fit the bound while preserving aspect ratio; smaller images
+    // are left untouched.
+    let bounded = image.thumbnail(MAX_DIMENSION, MAX_DIMENSION);
+
+    // Preserve transparency by keeping PNG for PNG/GIF/WebP sources that had
+    // it; everything else, other formats with alpha included, collapses to
+    // JPEG for a compact store.
+    let has_alpha = matches!(format, ImageFormat::Png | ImageFormat::Gif | ImageFormat::WebP)
+        && bounded.color().has_alpha();
+
+    let mut out = Cursor::new(Vec::new());
+    if has_alpha {
+        bounded
+            .write_to(&mut out, ImageOutputFormat::Png)
+            .map_err(|e| ApiError::Internal(e.to_string()))?;
+        Ok((out.into_inner(), "image/png"))
+    } else {
+        bounded
+            .write_to(&mut out, ImageOutputFormat::Jpeg(85))
+            .map_err(|e| ApiError::Internal(e.to_string()))?;
+        Ok((out.into_inner(), "image/jpeg"))
+    }
+}
+
+/// `POST /api/novels/:id/cover` — upload and store a novel's cover image.
+pub async fn upload_cover(
+    State(state): State<CoverState>,
+    user: AuthUser,
+    Path(id): Path<String>,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, ApiError> {
+    let object_id = ObjectId::parse_str(&id).map_err(|_| ApiError::InvalidObjectId)?;
+    authorize_owner(
+        state.novels.read_by_id(&object_id).await?.map(|n| n.owner_id),
+        &id,
+        &user,
+    )?;
+
+    // Pull the first file field out of the multipart body.
+    let field = multipart
+        .next_field()
+        .await
+        .map_err(|e| ApiError::BadRequest(format!("malformed multipart body: {}", e)))?
+        .ok_or_else(|| ApiError::BadRequest("no file field in multipart body".to_string()))?;
+    let bytes = field
+        .bytes()
+        .await
+        .map_err(|e| ApiError::BadRequest(format!("could not read upload: {}", e)))?;
+
+    if bytes.len() > state.max_upload_bytes {
+        return Err(ApiError::PayloadTooLarge(format!(
+            "upload exceeds the {}-byte limit",
+            state.max_upload_bytes
+        )));
+    }
+
+    let (normalized, content_type) = normalize_image(&bytes)?;
+    state.covers.store(&object_id, content_type, normalized).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({ "message": "Cover uploaded successfully", "content_type": content_type })),
+    ))
+}
+
+/// `GET /api/novels/:id/cover` — stream the stored cover image back.
+pub async fn get_cover(
+    State(state): State<CoverState>,
+    Path(id): Path<String>,
+) -> Result<Response, ApiError> {
+    let object_id = ObjectId::parse_str(&id).map_err(|_| ApiError::InvalidObjectId)?;
+    match state.covers.fetch(&object_id).await? {
+        Some(cover) => Ok(([(CONTENT_TYPE, cover.content_type)], cover.data).into_response()),
+        None => Err(ApiError::NotFound("Cover")),
+    }
+}
+
+/// Router mounting the cover endpoints with their shared state.
+pub fn cover_router(state: CoverState) -> Router {
+    Router::new()
+        .route("/api/novels/:id/cover", get(get_cover).post(upload_cover))
+        .with_state(state)
+}
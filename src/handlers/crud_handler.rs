@@ -1,258 +1,817 @@
 use axum::{
-    extract::{Path, State},
+    extract::{FromRef, Path, Query, State},
     http::StatusCode,
+    middleware::from_fn_with_state,
     response::{IntoResponse, Json},
     routing::{get, post, delete, patch},
     Router,
 };
 use mongodb::bson::{doc, oid::ObjectId};
+use serde::Deserialize;
+use tower_http::compression::{predicate::SizeAbove, CompressionLayer};
 use serde_json::{json, Value};
 use std::sync::Arc;
 
+use crate::auth::{Access, AuthState, AuthUser, TokenStore};
+use crate::auth::jwt::{auth_middleware, issue_jwt};
+use crate::handlers::error::{ApiError, ApiErrorBody};
 use crate::models::{Novel, Chapter, Character, QA};
+use crate::services::fulltext_search::{FullTextSearchService, Hit};
+use crate::services::pagination::{Cursor, ListOptions, Page};
 use crate::services::{NovelCrudService, ChapterCrudService, CharacterCrudService, QACrudService};
 use crate::services::crud_service::CrudService; // Import the CrudService trait
 
+/// Default and maximum number of hits a search endpoint returns.
+const DEFAULT_SEARCH_LIMIT: usize = 20;
+const MAX_SEARCH_LIMIT: usize = 100;
+
+/// Default page size for a list endpoint when `limit` is omitted.
+const DEFAULT_PAGE_LIMIT: i64 = 50;
+
+/// Responses smaller than this many bytes skip compression — the CPU cost and
+/// the few added header bytes outweigh any saving on tiny payloads.
+const MIN_COMPRESS_BYTES: u16 = 1024;
+
+/// Query string for the paginated list endpoints: `?limit=...&cursor=...`.
+#[derive(Debug, Deserialize)]
+pub struct ListParams {
+    pub limit: Option<i64>,
+    /// Opaque token from a previous page's `next_cursor`.
+    pub cursor: Option<String>,
+}
+
+/// State for a list endpoint: the CRUD service plus the server-configured page
+/// ceiling. The non-list handlers extract the bare `Arc<S>` via [`FromRef`].
+#[derive(Clone)]
+pub struct ListState<S> {
+    service: Arc<S>,
+    max_page_size: i64,
+}
+
+impl<S> ListState<S> {
+    fn new(service: Arc<S>, max_page_size: i64) -> Self {
+        Self { service, max_page_size }
+    }
+
+    /// Clamp the requested limit to `[1, max_page_size]`, defaulting when absent.
+    fn resolve_limit(&self, requested: Option<i64>) -> i64 {
+        requested
+            .unwrap_or(DEFAULT_PAGE_LIMIT)
+            .clamp(1, self.max_page_size)
+    }
+
+    /// Build the keyset options for a `_id`-ordered page from the query params.
+    fn list_options<K: crate::services::pagination::SortField>(
+        &self,
+        params: &ListParams,
+    ) -> ListOptions<K> {
+        ListOptions {
+            sort: Vec::new(),
+            limit: Some(self.resolve_limit(params.limit)),
+            after: params.cursor.clone().map(Cursor),
+        }
+    }
+}
+
+impl<S> FromRef<ListState<S>> for Arc<S> {
+    fn from_ref(state: &ListState<S>) -> Arc<S> {
+        state.service.clone()
+    }
+}
+
+/// Serialize a page as `{ "<key>": [...], "next_cursor": "..."? }`.
+fn page_response<T: serde::Serialize>(key: &str, page: Page<T>) -> Json<Value> {
+    let (items, next) = page;
+    Json(json!({
+        key: items,
+        "next_cursor": next.map(|cursor| cursor.0),
+    }))
+}
+
+/// Authorize a mutation against a parent novel's recorded owner and the
+/// caller's token scope.
+///
+/// `owner` is the result of a novel lookup: `None` means the novel is absent,
+/// `Some(None)` means it predates ownership tracking (no owner to enforce), and
+/// `Some(Some(id))` must equal the caller's id. Legacy ownerless novels stay
+/// writable so existing data keeps working after auth is switched on. A
+/// read-only token, or one scoped to other novels via `allowed_novel_ids`, is
+/// rejected before the ownership check regardless of who owns `novel_id`.
+pub(crate) fn authorize_owner(
+    owner: Option<Option<String>>,
+    novel_id: &str,
+    user: &AuthUser,
+) -> Result<(), ApiError> {
+    if !user.access.allows("write") {
+        return Err(ApiError::Forbidden);
+    }
+    if !user.allowed_novel_ids.is_empty() && !user.allowed_novel_ids.iter().any(|n| n == novel_id) {
+        return Err(ApiError::Forbidden);
+    }
+    match owner {
+        None => Err(ApiError::NovelNotFound),
+        Some(None) => Ok(()),
+        Some(Some(owner_id)) if owner_id == user.id => Ok(()),
+        Some(Some(_)) => Err(ApiError::Forbidden),
+    }
+}
+
+/// State shared by the login endpoint: the JWT signing secret and the token
+/// store that holds the provisioned API keys credentials are checked against.
+#[derive(Clone)]
+pub struct LoginState {
+    pub auth: AuthState,
+    pub token_store: Arc<TokenStore>,
+}
+
+/// Body of `POST /api/login`: a provisioned API key and the subject it was
+/// issued for.
+#[derive(Debug, Deserialize)]
+pub struct LoginRequest {
+    pub subject: String,
+    pub api_key: String,
+}
+
+/// `POST /api/login` — exchange a provisioned API key for a short-lived bearer
+/// token. The key must resolve through the [`TokenStore`] to the posted
+/// `subject`; an unknown, revoked, expired, or mismatched key is rejected, so a
+/// caller cannot mint a token for an identity it doesn't hold a key for.
+#[utoipa::path(
+    post, path = "/api/login", tag = "auth",
+    responses(
+        (status = 200, description = "A signed bearer token"),
+        (status = 400, description = "Missing subject or api_key", body = ApiErrorBody),
+        (status = 401, description = "Unknown or mismatched api_key", body = ApiErrorBody),
+    ),
+)]
+pub async fn login(
+    State(state): State<LoginState>,
+    Json(req): Json<LoginRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    if req.subject.trim().is_empty() || req.api_key.trim().is_empty() {
+        return Err(ApiError::BadRequest("subject and api_key are required".to_string()));
+    }
+    let resolved = state
+        .token_store
+        .resolve(req.api_key.trim())
+        .await
+        .map_err(|_| ApiError::Unauthorized)?
+        .filter(|resolved| resolved.subject == req.subject.trim())
+        .ok_or(ApiError::Unauthorized)?;
+    let token = issue_jwt(
+        &state.auth.secret,
+        &resolved.subject,
+        resolved.access,
+        resolved.allowed_novel_ids,
+    )?;
+    Ok((StatusCode::OK, Json(json!({ "token": token }))))
+}
+
+/// Query string for the fuzzy search endpoints: `?q=...&limit=...`.
+#[derive(Debug, Deserialize)]
+pub struct SearchQuery {
+    #[serde(default)]
+    pub q: String,
+    pub limit: Option<usize>,
+}
+
+impl SearchQuery {
+    /// The clamped hit limit, defaulting when absent.
+    fn limit(&self) -> usize {
+        self.limit.unwrap_or(DEFAULT_SEARCH_LIMIT).min(MAX_SEARCH_LIMIT)
+    }
+}
+
+/// Group a flat ranked hit list into `novel`/`chapter`/`character` buckets,
+/// preserving rank order within each bucket.
+fn group_hits(hits: &[Hit]) -> serde_json::Map<String, Value> {
+    let mut grouped = serde_json::Map::new();
+    for hit in hits {
+        grouped
+            .entry(hit.kind.clone())
+            .or_insert_with(|| Value::Array(Vec::new()))
+            .as_array_mut()
+            .expect("bucket is always an array")
+            .push(json!(hit));
+    }
+    grouped
+}
+
+// Fuzzy full-text search handlers
+
+/// `GET /api/search?q=...` — fuzzy search across every retrievable type.
+#[utoipa::path(
+    get, path = "/api/search", tag = "search",
+    params(
+        ("q" = String, Query, description = "Search query"),
+        ("limit" = Option<usize>, Query, description = "Maximum hits to return"),
+    ),
+    responses(
+        (status = 200, description = "Ranked hits plus a grouped-by-type view"),
+        (status = 400, description = "Missing query", body = ApiErrorBody),
+    ),
+)]
+pub async fn search(
+    State(search_service): State<Arc<FullTextSearchService>>,
+    Query(params): Query<SearchQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    if params.q.trim().is_empty() {
+        return Err(ApiError::BadRequest("query parameter 'q' is required".to_string()));
+    }
+    let hits = search_service.search(&params.q, params.limit()).await?;
+    let grouped = group_hits(&hits);
+    Ok((StatusCode::OK, Json(json!({ "hits": hits, "grouped": grouped }))))
+}
+
+/// `GET /api/search/:kind?q=...` — fuzzy search restricted to one type.
+#[utoipa::path(
+    get, path = "/api/search/{kind}", tag = "search",
+    params(
+        ("kind" = String, Path, description = "Document type: novel, chapter, or character"),
+        ("q" = String, Query, description = "Search query"),
+        ("limit" = Option<usize>, Query, description = "Maximum hits to return"),
+    ),
+    responses(
+        (status = 200, description = "Ranked hits of the requested type"),
+        (status = 400, description = "Missing query", body = ApiErrorBody),
+    ),
+)]
+pub async fn search_kind(
+    State(search_service): State<Arc<FullTextSearchService>>,
+    Path(kind): Path<String>,
+    Query(params): Query<SearchQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    if params.q.trim().is_empty() {
+        return Err(ApiError::BadRequest("query parameter 'q' is required".to_string()));
+    }
+    let hits = search_service
+        .search_kind(&kind, &params.q, params.limit())
+        .await?;
+    Ok((StatusCode::OK, Json(json!({ "hits": hits }))))
+}
+
 // Novel CRUD handlers
+#[utoipa::path(
+    get, path = "/api/novels", tag = "novels",
+    params(
+        ("limit" = Option<i64>, Query, description = "Maximum items in the page"),
+        ("cursor" = Option<String>, Query, description = "Opaque next-page cursor"),
+    ),
+    responses((status = 200, description = "A page of novels with an optional next_cursor")),
+)]
 pub async fn get_novels(
-    State(novel_service): State<Arc<NovelCrudService>>,
-) -> impl IntoResponse {
-    match novel_service.read_many(doc! {}, Some(100)).await {
-        Ok(novels) => (StatusCode::OK, Json(json!({ "novels": novels }))),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({ "error": e.to_string() })),
-        ),
-    }
+    State(state): State<ListState<NovelCrudService>>,
+    Query(params): Query<ListParams>,
+) -> Result<impl IntoResponse, ApiError> {
+    let page = state
+        .service
+        .read_page(doc! {}, state.list_options(&params))
+        .await?;
+    Ok((StatusCode::OK, page_response("novels", page)))
 }
 
+#[utoipa::path(
+    get, path = "/api/novels/{id}", tag = "novels",
+    params(("id" = String, Path, description = "Novel ObjectId")),
+    responses(
+        (status = 200, description = "The requested novel", body = Novel),
+        (status = 404, description = "Novel not found", body = ApiErrorBody),
+        (status = 400, description = "Malformed ObjectId", body = ApiErrorBody),
+    ),
+)]
 pub async fn get_novel(
     State(novel_service): State<Arc<NovelCrudService>>,
     Path(id): Path<String>,
-) -> impl IntoResponse {
-    match ObjectId::parse_str(&id) {
-        Ok(object_id) => match novel_service.read_by_id(&object_id).await {
-            Ok(Some(novel)) => (StatusCode::OK, Json(json!({ "novel": novel }))),
-            Ok(None) => (
-                StatusCode::NOT_FOUND,
-                Json(json!({ "error": "Novel not found" })),
-            ),
-            Err(e) => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({ "error": e.to_string() })),
-            ),
-        },
-        Err(_) => (
-            StatusCode::BAD_REQUEST,
-            Json(json!({ "error": "Invalid ObjectId format" })),
-        ),
+) -> Result<impl IntoResponse, ApiError> {
+    let object_id = ObjectId::parse_str(&id).map_err(|_| ApiError::InvalidObjectId)?;
+    match novel_service.read_by_id(&object_id).await? {
+        Some(novel) => Ok((StatusCode::OK, Json(json!({ "novel": novel })))),
+        None => Err(ApiError::NovelNotFound),
     }
 }
 
+#[utoipa::path(
+    post, path = "/api/novels", tag = "novels",
+    request_body = Novel,
+    responses((status = 201, description = "Novel created")),
+)]
 pub async fn create_novel(
     State(novel_service): State<Arc<NovelCrudService>>,
-    Json(novel): Json<Novel>,
-) -> impl IntoResponse {
-    match novel_service.create(&novel).await {
-        Ok(id) => (
-            StatusCode::CREATED,
-            Json(json!({ "id": id.to_string(), "message": "Novel created successfully" })),
-        ),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({ "error": e.to_string() })),
-        ),
-    }
+    user: AuthUser,
+    Json(mut novel): Json<Novel>,
+) -> Result<impl IntoResponse, ApiError> {
+    // Stamp the authenticated caller as the owner; subsequent mutations require
+    // the caller to match it.
+    novel.owner_id = Some(user.id);
+    let id = novel_service.create(&novel).await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({ "id": id.to_string(), "message": "Novel created successfully" })),
+    ))
 }
 
+#[utoipa::path(
+    patch, path = "/api/novels/{id}", tag = "novels",
+    params(("id" = String, Path, description = "Novel ObjectId")),
+    request_body = Object,
+    responses(
+        (status = 200, description = "Novel updated", body = Novel),
+        (status = 404, description = "Novel not found", body = ApiErrorBody),
+        (status = 400, description = "Malformed ObjectId or update data", body = ApiErrorBody),
+    ),
+)]
 pub async fn update_novel(
     State(novel_service): State<Arc<NovelCrudService>>,
+    user: AuthUser,
     Path(id): Path<String>,
     Json(update_data): Json<Value>,
-) -> impl IntoResponse {
-    match ObjectId::parse_str(&id) {
-        Ok(object_id) => {
-            // Convert serde_json::Value to MongoDB Document
-            let bson_doc = match mongodb::bson::to_document(&update_data) {
-                Ok(doc) => doc,
-                Err(e) => {
-                    return (
-                        StatusCode::BAD_REQUEST,
-                        Json(json!({ "error": format!("Invalid update data: {}", e) })),
-                    )
-                }
-            };
-
-            match novel_service.update(&object_id, bson_doc).await {
-                Ok(Some(novel)) => (
-                    StatusCode::OK,
-                    Json(json!({ "message": "Novel updated successfully", "novel": novel })),
-                ),
-                Ok(None) => (
-                    StatusCode::NOT_FOUND,
-                    Json(json!({ "error": "Novel not found" })),
-                ),
-                Err(e) => (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(json!({ "error": e.to_string() })),
-                ),
-            }
-        }
-        Err(_) => (
-            StatusCode::BAD_REQUEST,
-            Json(json!({ "error": "Invalid ObjectId format" })),
-        ),
+) -> Result<impl IntoResponse, ApiError> {
+    let object_id = ObjectId::parse_str(&id).map_err(|_| ApiError::InvalidObjectId)?;
+    authorize_owner(
+        novel_service.read_by_id(&object_id).await?.map(|n| n.owner_id),
+        &id,
+        &user,
+    )?;
+    // Convert serde_json::Value to MongoDB Document
+    let bson_doc = mongodb::bson::to_document(&update_data)
+        .map_err(|e| ApiError::InvalidUpdateData(e.to_string()))?;
+
+    match novel_service.update(&object_id, bson_doc).await? {
+        Some(novel) => Ok((
+            StatusCode::OK,
+            Json(json!({ "message": "Novel updated successfully", "novel": novel })),
+        )),
+        None => Err(ApiError::NovelNotFound),
     }
 }
 
+/// Query flag for [`delete_novel`]: `?cascade=true` also removes the novel's
+/// chapters and characters.
+#[derive(Debug, Deserialize)]
+pub struct DeleteParams {
+    #[serde(default)]
+    pub cascade: bool,
+}
+
+#[utoipa::path(
+    delete, path = "/api/novels/{id}", tag = "novels",
+    params(
+        ("id" = String, Path, description = "Novel ObjectId"),
+        ("cascade" = Option<bool>, Query, description = "Also delete the novel's chapters and characters"),
+    ),
+    responses(
+        (status = 200, description = "Novel deleted"),
+        (status = 404, description = "Novel not found", body = ApiErrorBody),
+        (status = 400, description = "Malformed ObjectId", body = ApiErrorBody),
+    ),
+)]
 pub async fn delete_novel(
     State(novel_service): State<Arc<NovelCrudService>>,
+    user: AuthUser,
     Path(id): Path<String>,
-) -> impl IntoResponse {
-    match ObjectId::parse_str(&id) {
-        Ok(object_id) => match novel_service.delete(&object_id).await {
-            Ok(true) => (
-                StatusCode::OK,
-                Json(json!({ "message": "Novel deleted successfully" })),
-            ),
-            Ok(false) => (
-                StatusCode::NOT_FOUND,
-                Json(json!({ "error": "Novel not found" })),
-            ),
-            Err(e) => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({ "error": e.to_string() })),
-            ),
-        },
-        Err(_) => (
-            StatusCode::BAD_REQUEST,
-            Json(json!({ "error": "Invalid ObjectId format" })),
-        ),
+    Query(params): Query<DeleteParams>,
+) -> Result<impl IntoResponse, ApiError> {
+    let object_id = ObjectId::parse_str(&id).map_err(|_| ApiError::InvalidObjectId)?;
+    authorize_owner(
+        novel_service.read_by_id(&object_id).await?.map(|n| n.owner_id),
+        &id,
+        &user,
+    )?;
+    let deleted = if params.cascade {
+        novel_service.delete_cascade(&object_id).await?
+    } else {
+        novel_service.delete(&object_id).await?
+    };
+    if deleted {
+        Ok((
+            StatusCode::OK,
+            Json(json!({ "message": "Novel deleted successfully" })),
+        ))
+    } else {
+        Err(ApiError::NovelNotFound)
     }
 }
 
 // Chapter CRUD handlers
+#[utoipa::path(
+    get, path = "/api/chapters", tag = "chapters",
+    params(
+        ("limit" = Option<i64>, Query, description = "Maximum items in the page"),
+        ("cursor" = Option<String>, Query, description = "Opaque next-page cursor"),
+    ),
+    responses((status = 200, description = "A page of chapters with an optional next_cursor")),
+)]
 pub async fn get_chapters(
-    State(chapter_service): State<Arc<ChapterCrudService>>,
-) -> impl IntoResponse {
-    match chapter_service.read_many(doc! {}, Some(100)).await {
-        Ok(chapters) => (StatusCode::OK, Json(json!({ "chapters": chapters }))),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({ "error": e.to_string() })),
-        ),
-    }
+    State(state): State<ListState<ChapterCrudService>>,
+    Query(params): Query<ListParams>,
+) -> Result<impl IntoResponse, ApiError> {
+    let page = state
+        .service
+        .read_page(doc! {}, state.list_options(&params))
+        .await?;
+    Ok((StatusCode::OK, page_response("chapters", page)))
 }
 
+#[utoipa::path(
+    get, path = "/api/novels/{id}/chapters", tag = "chapters",
+    params(("id" = String, Path, description = "Novel ObjectId")),
+    responses(
+        (status = 200, description = "Chapters belonging to the novel"),
+        (status = 400, description = "Malformed ObjectId", body = ApiErrorBody),
+    ),
+)]
 pub async fn get_novel_chapters(
     State(chapter_service): State<Arc<ChapterCrudService>>,
     Path(novel_id): Path<String>,
-) -> impl IntoResponse {
-    match ObjectId::parse_str(&novel_id) {
-        Ok(object_id) => match chapter_service.find_by_novel_id(&object_id).await {
-            Ok(chapters) => (StatusCode::OK, Json(json!({ "chapters": chapters }))),
-            Err(e) => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({ "error": e.to_string() })),
-            ),
-        },
-        Err(_) => (
-            StatusCode::BAD_REQUEST,
-            Json(json!({ "error": "Invalid ObjectId format" })),
-        ),
-    }
+) -> Result<impl IntoResponse, ApiError> {
+    let object_id = ObjectId::parse_str(&novel_id).map_err(|_| ApiError::InvalidObjectId)?;
+    let chapters = chapter_service.find_by_novel_id(&object_id).await?;
+    Ok((StatusCode::OK, Json(json!({ "chapters": chapters }))))
 }
 
+#[utoipa::path(
+    post, path = "/api/chapters", tag = "chapters",
+    request_body = Chapter,
+    responses((status = 201, description = "Chapter created")),
+)]
 pub async fn create_chapter(
     State(chapter_service): State<Arc<ChapterCrudService>>,
+    user: AuthUser,
     Json(chapter): Json<Chapter>,
-) -> impl IntoResponse {
-    match chapter_service.create(&chapter).await {
-        Ok(id) => (
-            StatusCode::CREATED,
-            Json(json!({ "id": id.to_string(), "message": "Chapter created successfully" })),
-        ),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({ "error": e.to_string() })),
-        ),
+) -> Result<impl IntoResponse, ApiError> {
+    authorize_owner(
+        chapter_service.parent_novel_owner(&chapter.novel_id).await?,
+        &chapter.novel_id.to_string(),
+        &user,
+    )?;
+    let id = chapter_service.create(&chapter).await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({ "id": id.to_string(), "message": "Chapter created successfully" })),
+    ))
+}
+
+#[utoipa::path(
+    get, path = "/api/chapters/{id}", tag = "chapters",
+    params(("id" = String, Path, description = "Chapter ObjectId")),
+    responses(
+        (status = 200, description = "The requested chapter", body = Chapter),
+        (status = 404, description = "Chapter not found", body = ApiErrorBody),
+        (status = 400, description = "Malformed ObjectId", body = ApiErrorBody),
+    ),
+)]
+pub async fn get_chapter(
+    State(chapter_service): State<Arc<ChapterCrudService>>,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    let object_id = ObjectId::parse_str(&id).map_err(|_| ApiError::InvalidObjectId)?;
+    match chapter_service.read_by_id(&object_id).await? {
+        Some(chapter) => Ok((StatusCode::OK, Json(json!({ "chapter": chapter })))),
+        None => Err(ApiError::NotFound("Chapter")),
+    }
+}
+
+#[utoipa::path(
+    patch, path = "/api/chapters/{id}", tag = "chapters",
+    params(("id" = String, Path, description = "Chapter ObjectId")),
+    request_body = Object,
+    responses(
+        (status = 200, description = "Chapter updated", body = Chapter),
+        (status = 404, description = "Chapter not found", body = ApiErrorBody),
+        (status = 400, description = "Malformed ObjectId or update data", body = ApiErrorBody),
+    ),
+)]
+pub async fn update_chapter(
+    State(chapter_service): State<Arc<ChapterCrudService>>,
+    Path(id): Path<String>,
+    Json(update_data): Json<Value>,
+) -> Result<impl IntoResponse, ApiError> {
+    let object_id = ObjectId::parse_str(&id).map_err(|_| ApiError::InvalidObjectId)?;
+    let bson_doc = mongodb::bson::to_document(&update_data)
+        .map_err(|e| ApiError::InvalidUpdateData(e.to_string()))?;
+
+    match chapter_service.update(&object_id, bson_doc).await? {
+        Some(chapter) => Ok((
+            StatusCode::OK,
+            Json(json!({ "message": "Chapter updated successfully", "chapter": chapter })),
+        )),
+        None => Err(ApiError::NotFound("Chapter")),
+    }
+}
+
+#[utoipa::path(
+    delete, path = "/api/chapters/{id}", tag = "chapters",
+    params(("id" = String, Path, description = "Chapter ObjectId")),
+    responses(
+        (status = 200, description = "Chapter deleted"),
+        (status = 404, description = "Chapter not found", body = ApiErrorBody),
+        (status = 400, description = "Malformed ObjectId", body = ApiErrorBody),
+    ),
+)]
+pub async fn delete_chapter(
+    State(chapter_service): State<Arc<ChapterCrudService>>,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    let object_id = ObjectId::parse_str(&id).map_err(|_| ApiError::InvalidObjectId)?;
+    if chapter_service.delete(&object_id).await? {
+        Ok((
+            StatusCode::OK,
+            Json(json!({ "message": "Chapter deleted successfully" })),
+        ))
+    } else {
+        Err(ApiError::NotFound("Chapter"))
     }
 }
 
 // Character CRUD handlers
+#[utoipa::path(
+    get, path = "/api/characters", tag = "characters",
+    params(
+        ("limit" = Option<i64>, Query, description = "Maximum items in the page"),
+        ("cursor" = Option<String>, Query, description = "Opaque next-page cursor"),
+    ),
+    responses((status = 200, description = "A page of characters with an optional next_cursor")),
+)]
 pub async fn get_characters(
-    State(character_service): State<Arc<CharacterCrudService>>,
-) -> impl IntoResponse {
-    match character_service.read_many(doc! {}, Some(100)).await {
-        Ok(characters) => (StatusCode::OK, Json(json!({ "characters": characters }))),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({ "error": e.to_string() })),
-        ),
-    }
+    State(state): State<ListState<CharacterCrudService>>,
+    Query(params): Query<ListParams>,
+) -> Result<impl IntoResponse, ApiError> {
+    let page = state
+        .service
+        .read_page(doc! {}, state.list_options(&params))
+        .await?;
+    Ok((StatusCode::OK, page_response("characters", page)))
 }
 
+#[utoipa::path(
+    get, path = "/api/novels/{id}/characters", tag = "characters",
+    params(("id" = String, Path, description = "Novel ObjectId")),
+    responses(
+        (status = 200, description = "Characters belonging to the novel"),
+        (status = 400, description = "Malformed ObjectId", body = ApiErrorBody),
+    ),
+)]
 pub async fn get_novel_characters(
     State(character_service): State<Arc<CharacterCrudService>>,
     Path(novel_id): Path<String>,
-) -> impl IntoResponse {
-    match ObjectId::parse_str(&novel_id) {
-        Ok(object_id) => match character_service.find_by_novel_id(&object_id).await {
-            Ok(characters) => (StatusCode::OK, Json(json!({ "characters": characters }))),
-            Err(e) => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({ "error": e.to_string() })),
-            ),
-        },
-        Err(_) => (
-            StatusCode::BAD_REQUEST,
-            Json(json!({ "error": "Invalid ObjectId format" })),
-        ),
-    }
+) -> Result<impl IntoResponse, ApiError> {
+    let object_id = ObjectId::parse_str(&novel_id).map_err(|_| ApiError::InvalidObjectId)?;
+    let characters = character_service.find_by_novel_id(&object_id).await?;
+    Ok((StatusCode::OK, Json(json!({ "characters": characters }))))
 }
 
+#[utoipa::path(
+    post, path = "/api/characters", tag = "characters",
+    request_body = Character,
+    responses((status = 201, description = "Character created")),
+)]
 pub async fn create_character(
     State(character_service): State<Arc<CharacterCrudService>>,
+    user: AuthUser,
     Json(character): Json<Character>,
-) -> impl IntoResponse {
-    match character_service.create(&character).await {
-        Ok(id) => (
-            StatusCode::CREATED,
-            Json(json!({ "id": id.to_string(), "message": "Character created successfully" })),
-        ),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({ "error": e.to_string() })),
-        ),
+) -> Result<impl IntoResponse, ApiError> {
+    authorize_owner(
+        character_service.parent_novel_owner(&character.novel_id).await?,
+        &character.novel_id.to_string(),
+        &user,
+    )?;
+    let id = character_service.create(&character).await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({ "id": id.to_string(), "message": "Character created successfully" })),
+    ))
+}
+
+#[utoipa::path(
+    get, path = "/api/characters/{id}", tag = "characters",
+    params(("id" = String, Path, description = "Character ObjectId")),
+    responses(
+        (status = 200, description = "The requested character", body = Character),
+        (status = 404, description = "Character not found", body = ApiErrorBody),
+        (status = 400, description = "Malformed ObjectId", body = ApiErrorBody),
+    ),
+)]
+pub async fn get_character(
+    State(character_service): State<Arc<CharacterCrudService>>,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    let object_id = ObjectId::parse_str(&id).map_err(|_| ApiError::InvalidObjectId)?;
+    match character_service.read_by_id(&object_id).await? {
+        Some(character) => Ok((StatusCode::OK, Json(json!({ "character": character })))),
+        None => Err(ApiError::NotFound("Character")),
+    }
+}
+
+#[utoipa::path(
+    patch, path = "/api/characters/{id}", tag = "characters",
+    params(("id" = String, Path, description = "Character ObjectId")),
+    request_body = Object,
+    responses(
+        (status = 200, description = "Character updated", body = Character),
+        (status = 404, description = "Character not found", body = ApiErrorBody),
+        (status = 400, description = "Malformed ObjectId or update data", body = ApiErrorBody),
+    ),
+)]
+pub async fn update_character(
+    State(character_service): State<Arc<CharacterCrudService>>,
+    Path(id): Path<String>,
+    Json(update_data): Json<Value>,
+) -> Result<impl IntoResponse, ApiError> {
+    let object_id = ObjectId::parse_str(&id).map_err(|_| ApiError::InvalidObjectId)?;
+    let bson_doc = mongodb::bson::to_document(&update_data)
+        .map_err(|e| ApiError::InvalidUpdateData(e.to_string()))?;
+
+    match character_service.update(&object_id, bson_doc).await? {
+        Some(character) => Ok((
+            StatusCode::OK,
+            Json(json!({ "message": "Character updated successfully", "character": character })),
+        )),
+        None => Err(ApiError::NotFound("Character")),
+    }
+}
+
+#[utoipa::path(
+    delete, path = "/api/characters/{id}", tag = "characters",
+    params(("id" = String, Path, description = "Character ObjectId")),
+    responses(
+        (status = 200, description = "Character deleted"),
+        (status = 404, description = "Character not found", body = ApiErrorBody),
+        (status = 400, description = "Malformed ObjectId", body = ApiErrorBody),
+    ),
+)]
+pub async fn delete_character(
+    State(character_service): State<Arc<CharacterCrudService>>,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    let object_id = ObjectId::parse_str(&id).map_err(|_| ApiError::InvalidObjectId)?;
+    if character_service.delete(&object_id).await? {
+        Ok((
+            StatusCode::OK,
+            Json(json!({ "message": "Character deleted successfully" })),
+        ))
+    } else {
+        Err(ApiError::NotFound("Character"))
     }
 }
 
 // QA CRUD handlers
+#[utoipa::path(
+    get, path = "/api/qa", tag = "qa",
+    params(
+        ("limit" = Option<i64>, Query, description = "Maximum items in the page"),
+        ("cursor" = Option<String>, Query, description = "Opaque next-page cursor"),
+    ),
+    responses((status = 200, description = "A page of Q&A entries with an optional next_cursor")),
+)]
 pub async fn get_qa_entries(
-    State(qa_service): State<Arc<QACrudService>>,
-) -> impl IntoResponse {
-    match qa_service.read_many(doc! {}, Some(100)).await {
-        Ok(qa_entries) => (StatusCode::OK, Json(json!({ "qa_entries": qa_entries }))),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({ "error": e.to_string() })),
-        ),
-    }
+    State(state): State<ListState<QACrudService>>,
+    Query(params): Query<ListParams>,
+) -> Result<impl IntoResponse, ApiError> {
+    let page = state
+        .service
+        .read_page(doc! {}, state.list_options(&params))
+        .await?;
+    Ok((StatusCode::OK, page_response("qa_entries", page)))
 }
 
+#[utoipa::path(
+    post, path = "/api/qa", tag = "qa",
+    request_body = QA,
+    responses((status = 201, description = "Q&A entry created")),
+)]
 pub async fn create_qa(
     State(qa_service): State<Arc<QACrudService>>,
     Json(qa): Json<QA>,
-) -> impl IntoResponse {
-    match qa_service.create(&qa).await {
-        Ok(id) => (
-            StatusCode::CREATED,
-            Json(json!({ "id": id.to_string(), "message": "QA created successfully" })),
-        ),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({ "error": e.to_string() })),
-        ),
+) -> Result<impl IntoResponse, ApiError> {
+    let id = qa_service.create(&qa).await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({ "id": id.to_string(), "message": "QA created successfully" })),
+    ))
+}
+
+#[utoipa::path(
+    get, path = "/api/qa/{id}", tag = "qa",
+    params(("id" = String, Path, description = "Q&A entry ObjectId")),
+    responses(
+        (status = 200, description = "The requested Q&A entry", body = QA),
+        (status = 404, description = "Q&A entry not found", body = ApiErrorBody),
+        (status = 400, description = "Malformed ObjectId", body = ApiErrorBody),
+    ),
+)]
+pub async fn get_qa(
+    State(qa_service): State<Arc<QACrudService>>,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    let object_id = ObjectId::parse_str(&id).map_err(|_| ApiError::InvalidObjectId)?;
+    match qa_service.read_by_id(&object_id).await? {
+        Some(qa) => Ok((StatusCode::OK, Json(json!({ "qa": qa })))),
+        None => Err(ApiError::NotFound("Q&A entry")),
+    }
+}
+
+#[utoipa::path(
+    patch, path = "/api/qa/{id}", tag = "qa",
+    params(("id" = String, Path, description = "Q&A entry ObjectId")),
+    request_body = Object,
+    responses(
+        (status = 200, description = "Q&A entry updated", body = QA),
+        (status = 404, description = "Q&A entry not found", body = ApiErrorBody),
+        (status = 400, description = "Malformed ObjectId or update data", body = ApiErrorBody),
+    ),
+)]
+pub async fn update_qa(
+    State(qa_service): State<Arc<QACrudService>>,
+    Path(id): Path<String>,
+    Json(update_data): Json<Value>,
+) -> Result<impl IntoResponse, ApiError> {
+    let object_id = ObjectId::parse_str(&id).map_err(|_| ApiError::InvalidObjectId)?;
+    let bson_doc = mongodb::bson::to_document(&update_data)
+        .map_err(|e| ApiError::InvalidUpdateData(e.to_string()))?;
+
+    match qa_service.update(&object_id, bson_doc).await? {
+        Some(qa) => Ok((
+            StatusCode::OK,
+            Json(json!({ "message": "Q&A entry updated successfully", "qa": qa })),
+        )),
+        None => Err(ApiError::NotFound("Q&A entry")),
+    }
+}
+
+#[utoipa::path(
+    delete, path = "/api/qa/{id}", tag = "qa",
+    params(("id" = String, Path, description = "Q&A entry ObjectId")),
+    responses(
+        (status = 200, description = "Q&A entry deleted"),
+        (status = 404, description = "Q&A entry not found", body = ApiErrorBody),
+        (status = 400, description = "Malformed ObjectId", body = ApiErrorBody),
+    ),
+)]
+pub async fn delete_qa(
+    State(qa_service): State<Arc<QACrudService>>,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    let object_id = ObjectId::parse_str(&id).map_err(|_| ApiError::InvalidObjectId)?;
+    if qa_service.delete(&object_id).await? {
+        Ok((
+            StatusCode::OK,
+            Json(json!({ "message": "Q&A entry deleted successfully" })),
+        ))
+    } else {
+        Err(ApiError::NotFound("Q&A entry"))
+    }
+}
+
+// Token lifecycle handlers
+
+pub async fn issue_token(
+    State(token_store): State<Arc<TokenStore>>,
+    Json(req): Json<Value>,
+) -> Result<impl IntoResponse, ApiError> {
+    let subject = req["subject"].as_str().unwrap_or_default().to_string();
+    let label = req["label"].as_str().map(String::from);
+    let collections: Vec<String> = req["allowed_collections"]
+        .as_array()
+        .map(|a| a.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+        .unwrap_or_default();
+    let novel_ids: Vec<String> = req["allowed_novel_ids"]
+        .as_array()
+        .map(|a| a.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+        .unwrap_or_default();
+    let access = match req["access"].as_str() {
+        Some("read_write") => Access::ReadWrite,
+        _ => Access::ReadOnly,
+    };
+
+    if subject.is_empty() {
+        return Err(ApiError::BadRequest("subject is required".to_string()));
+    }
+
+    let (raw, id) = token_store
+        .issue(&subject, label, collections, novel_ids, access, None)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({ "id": id.to_string(), "token": raw })),
+    ))
+}
+
+pub async fn list_tokens(
+    State(token_store): State<Arc<TokenStore>>,
+) -> Result<impl IntoResponse, ApiError> {
+    let tokens = token_store.list().await?;
+    Ok((StatusCode::OK, Json(json!({ "tokens": tokens }))))
+}
+
+pub async fn revoke_token(
+    State(token_store): State<Arc<TokenStore>>,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    let object_id = ObjectId::parse_str(&id).map_err(|_| ApiError::InvalidObjectId)?;
+    if token_store.revoke(&object_id).await? {
+        Ok((StatusCode::OK, Json(json!({ "message": "Token revoked" }))))
+    } else {
+        Err(ApiError::NotFound("Token"))
     }
 }
 
@@ -262,37 +821,171 @@ pub fn api_router(
     chapter_service: Arc<ChapterCrudService>,
     character_service: Arc<CharacterCrudService>,
     qa_service: Arc<QACrudService>,
+    token_store: Arc<TokenStore>,
+    search_service: Arc<FullTextSearchService>,
+    cover_service: Arc<crate::services::cover_service::CoverService>,
+    max_page_size: i64,
+    max_upload_bytes: usize,
+    jwt_secret: String,
+    protect_reads: bool,
 ) -> Router {
-    // Create separate routers for each service with their own state
+    // Shared auth layer: validates the bearer token (when present), injects the
+    // `AuthUser`, and — when `protect_reads` is set — rejects unauthenticated
+    // reads too. Novel create/update/delete, chapter and character creation,
+    // and cover upload require `AuthUser` regardless; the chapter, character
+    // and Q&A update/delete handlers, Q&A creation, and the token endpoints
+    // take no `AuthUser` and are gated only when `protect_reads` is set.
+    let auth_state = AuthState {
+        secret: jwt_secret,
+        protect_reads,
+    };
+    let auth_layer = || from_fn_with_state(auth_state.clone(), auth_middleware);
+
+    // Create separate routers for each service with their own state. The list
+    // endpoints carry the page ceiling alongside the service; the other
+    // handlers extract the bare service via `FromRef`.
+    // The cover endpoints reuse the novel service for ownership checks, so keep
+    // a handle before the list state takes ownership.
+    let cover_state = crate::handlers::cover_handler::CoverState {
+        covers: cover_service,
+        novels: novel_service.clone(),
+        max_upload_bytes,
+    };
+
     let novel_router = Router::new()
         .route("/api/novels", get(get_novels))
         .route("/api/novels", post(create_novel))
         .route("/api/novels/:id", get(get_novel))
         .route("/api/novels/:id", patch(update_novel))
         .route("/api/novels/:id", delete(delete_novel))
-        .with_state(novel_service);
-        
+        .with_state(ListState::new(novel_service, max_page_size))
+        .layer(auth_layer());
+
+    let cover_router = crate::handlers::cover_handler::cover_router(cover_state)
+        .layer(auth_layer());
+
     let chapter_router = Router::new()
         .route("/api/chapters", get(get_chapters))
         .route("/api/chapters", post(create_chapter))
+        .route("/api/chapters/:id", get(get_chapter))
+        .route("/api/chapters/:id", patch(update_chapter))
+        .route("/api/chapters/:id", delete(delete_chapter))
         .route("/api/novels/:id/chapters", get(get_novel_chapters))
-        .with_state(chapter_service);
-        
+        .with_state(ListState::new(chapter_service, max_page_size))
+        .layer(auth_layer());
+
     let character_router = Router::new()
         .route("/api/characters", get(get_characters))
         .route("/api/characters", post(create_character))
+        .route("/api/characters/:id", get(get_character))
+        .route("/api/characters/:id", patch(update_character))
+        .route("/api/characters/:id", delete(delete_character))
         .route("/api/novels/:id/characters", get(get_novel_characters))
-        .with_state(character_service);
-        
+        .with_state(ListState::new(character_service, max_page_size))
+        .layer(auth_layer());
+
     let qa_router = Router::new()
         .route("/api/qa", get(get_qa_entries))
         .route("/api/qa", post(create_qa))
-        .with_state(qa_service);
-    
-    // Merge all the routers
+        .route("/api/qa/:id", get(get_qa))
+        .route("/api/qa/:id", patch(update_qa))
+        .route("/api/qa/:id", delete(delete_qa))
+        .with_state(ListState::new(qa_service, max_page_size))
+        .layer(auth_layer());
+
+    let auth_router = Router::new()
+        .route("/api/login", post(login))
+        .with_state(LoginState {
+            auth: auth_state,
+            token_store: token_store.clone(),
+        });
+
+    let token_router = Router::new()
+        .route("/api/tokens", get(list_tokens))
+        .route("/api/tokens", post(issue_token))
+        .route("/api/tokens/:id", delete(revoke_token))
+        .with_state(token_store)
+        .layer(auth_layer());
+
+    let search_router = Router::new()
+        .route("/api/search", get(search))
+        .route("/api/search/:kind", get(search_kind))
+        .with_state(search_service)
+        .layer(auth_layer());
+
+    // Merge all the routers, mounting the generated Swagger UI / spec alongside.
     Router::new()
         .merge(novel_router)
         .merge(chapter_router)
         .merge(character_router)
         .merge(qa_router)
+        .merge(token_router)
+        .merge(search_router)
+        .merge(cover_router)
+        .merge(auth_router)
+        .merge(crate::handlers::openapi::swagger_ui())
+        // Negotiate gzip/brotli/zstd from the client's `Accept-Encoding`,
+        // skipping responses below the threshold. Bulk reads and highlighted
+        // search bodies compress well; no handler changes are needed.
+        .layer(CompressionLayer::new().compress_when(SizeAbove::new(MIN_COMPRESS_BYTES)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(id: &str) -> AuthUser {
+        AuthUser {
+            id: id.to_string(),
+            access: Access::ReadWrite,
+            allowed_novel_ids: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn missing_novel_is_not_found() {
+        assert!(matches!(
+            authorize_owner(None, "novel-1", &user("alice")),
+            Err(ApiError::NovelNotFound)
+        ));
+    }
+
+    #[test]
+    fn ownerless_novel_stays_writable() {
+        assert!(authorize_owner(Some(None), "novel-1", &user("alice")).is_ok());
+    }
+
+    #[test]
+    fn matching_owner_is_authorized() {
+        assert!(authorize_owner(Some(Some("alice".to_string())), "novel-1", &user("alice")).is_ok());
+    }
+
+    #[test]
+    fn mismatched_owner_is_forbidden() {
+        assert!(matches!(
+            authorize_owner(Some(Some("alice".to_string())), "novel-1", &user("bob")),
+            Err(ApiError::Forbidden)
+        ));
+    }
+
+    #[test]
+    fn read_only_token_cannot_authorize_a_mutation() {
+        let mut user = user("alice");
+        user.access = Access::ReadOnly;
+        assert!(matches!(
+            authorize_owner(Some(Some("alice".to_string())), "novel-1", &user),
+            Err(ApiError::Forbidden)
+        ));
+    }
+
+    #[test]
+    fn novel_scoped_token_is_forbidden_outside_its_scope() {
+        let mut user = user("alice");
+        user.allowed_novel_ids = vec!["novel-1".to_string()];
+        assert!(authorize_owner(Some(Some("alice".to_string())), "novel-1", &user).is_ok());
+        assert!(matches!(
+            authorize_owner(Some(Some("alice".to_string())), "novel-2", &user),
+            Err(ApiError::Forbidden)
+        ));
+    }
 }
\ No newline at end of file
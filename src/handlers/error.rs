@@ -0,0 +1,131 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Json},
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Base URL under which each `error_code` has a documentation page.
+const ERROR_DOC_BASE: &str = "https://docs.novel-server.dev/errors";
+
+/// The JSON body every [`ApiError`] serializes to, documented in the OpenAPI
+/// spec so clients know the shape of a failure response.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ApiErrorBody {
+    /// Human-readable description of what went wrong.
+    pub message: String,
+    /// Stable, machine-readable code clients branch on.
+    pub error_code: String,
+    /// Broad category: `invalid_request`, `auth`, or `internal`.
+    pub error_type: String,
+    /// Link to the documentation page for `error_code`.
+    pub error_link: String,
+}
+
+/// Structured error returned by the CRUD, search, login and cover handlers.
+///
+/// Serializing through [`IntoResponse`] yields a stable body with a human
+/// `message`, a machine `error_code`, an `error_type` category, and an
+/// `error_link` to the docs, so consumers branch on `error_code` rather than
+/// string-matching messages.
+#[derive(Debug)]
+pub enum ApiError {
+    /// A path parameter was not a well-formed ObjectId.
+    InvalidObjectId,
+    /// The requested novel does not exist.
+    NovelNotFound,
+    /// A generic "document not found" carrying the entity name.
+    NotFound(&'static str),
+    /// An update payload could not be converted to a BSON document.
+    InvalidUpdateData(String),
+    /// A request field required by the handler was missing or empty.
+    BadRequest(String),
+    /// An uploaded payload exceeded the configured size limit.
+    PayloadTooLarge(String),
+    /// An uploaded payload was not a media type the handler accepts.
+    UnsupportedMediaType(String),
+    /// Authentication is required but absent or invalid.
+    Unauthorized,
+    /// The caller is authenticated but does not own the target resource, or
+    /// its token's scope does not allow the write.
+    Forbidden,
+    /// Any unexpected failure (Mongo errors, BSON conversion, etc.).
+    Internal(String),
+}
+
+impl ApiError {
+    /// Stable, machine-readable code clients can branch on.
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            ApiError::InvalidObjectId => "invalid_object_id",
+            ApiError::NovelNotFound => "novel_not_found",
+            ApiError::NotFound(_) => "not_found",
+            ApiError::InvalidUpdateData(_) => "invalid_update_data",
+            ApiError::BadRequest(_) => "bad_request",
+            ApiError::PayloadTooLarge(_) => "payload_too_large",
+            ApiError::UnsupportedMediaType(_) => "unsupported_media_type",
+            ApiError::Unauthorized => "unauthorized",
+            ApiError::Forbidden => "forbidden",
+            ApiError::Internal(_) => "internal_error",
+        }
+    }
+
+    /// Broad category distinguishing caller mistakes from server faults.
+    pub fn error_type(&self) -> &'static str {
+        match self {
+            ApiError::Internal(_) => "internal",
+            ApiError::Unauthorized | ApiError::Forbidden => "auth",
+            _ => "invalid_request",
+        }
+    }
+
+    /// HTTP status carried by this variant.
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ApiError::InvalidObjectId
+            | ApiError::InvalidUpdateData(_)
+            | ApiError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            ApiError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            ApiError::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            ApiError::Unauthorized => StatusCode::UNAUTHORIZED,
+            ApiError::Forbidden => StatusCode::FORBIDDEN,
+            ApiError::NovelNotFound | ApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// Human-readable message.
+    pub fn message(&self) -> String {
+        match self {
+            ApiError::InvalidObjectId => "Invalid ObjectId format".to_string(),
+            ApiError::NovelNotFound => "Novel not found".to_string(),
+            ApiError::NotFound(what) => format!("{} not found", what),
+            ApiError::InvalidUpdateData(reason) => format!("Invalid update data: {}", reason),
+            ApiError::BadRequest(reason) => reason.clone(),
+            ApiError::PayloadTooLarge(reason) => reason.clone(),
+            ApiError::UnsupportedMediaType(reason) => reason.clone(),
+            ApiError::Unauthorized => "Authentication required".to_string(),
+            ApiError::Forbidden => "You are not permitted to modify this resource".to_string(),
+            ApiError::Internal(reason) => reason.clone(),
+        }
+    }
+}
+
+/// Any [`anyhow::Error`] bubbling out of a service becomes an internal error.
+impl From<anyhow::Error> for ApiError {
+    fn from(err: anyhow::Error) -> Self {
+        ApiError::Internal(err.to_string())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> axum::response::Response {
+        let body = Json(ApiErrorBody {
+            error_link: format!("{}/{}", ERROR_DOC_BASE, self.error_code()),
+            message: self.message(),
+            error_code: self.error_code().to_string(),
+            error_type: self.error_type().to_string(),
+        });
+        (self.status(), body).into_response()
+    }
+}
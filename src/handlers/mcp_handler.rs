@@ -26,37 +26,313 @@ use std::convert::Infallible;
 use std::time::Duration;
 use tokio_stream::wrappers::IntervalStream;
 
-use crate::models::{SearchParams, domain::MCPResponse};
+use crate::models::{SearchParams, McpError, domain::MCPResponse};
+use crate::mcp::subscription::{new_registry, ReqFilter, Registry};
+use crate::mcp::tool_schema::ToolSchema;
+use crate::mcp::cursor::Cursor;
+use crate::mcp::compression;
+use crate::mcp::budget;
+use crate::auth::{Authorization, TokenStore};
+use crate::services::cache::{cache_key, ResultCache};
 use crate::services::db_service::DatabaseService;
-use crate::utils::QueryParser;
+use crate::services::synonyms::Synonyms;
+use crate::utils::{validate_auth_token, AllowedPrincipals, ERROR_UNAUTHORIZED, QueryParser};
 use crate::mcp::conversion::ContentExt;
 use crate::utils::query_parser;
 use std::error::Error;
 use serde_json::Value;
 use uuid::Uuid;
-use std::io::{BufRead, Write};
+use mongodb::bson::oid::ObjectId;
+
+/// Default page size, tuned for the advertised ~3k-token context window so a
+/// small-context client can walk a large result set a page at a time.
+pub const DEFAULT_PAGE_SIZE: usize = 5;
 
 /// Server state for handling MCP requests
 #[derive(Clone)]
 pub struct ServerState<T: DatabaseService> {
     pub db_service: T,
+    /// Live-update subscriptions keyed by id, shared with the SSE endpoint.
+    pub subscriptions: Registry,
+    /// Default number of items returned per page when a cursor omits one.
+    pub page_size: usize,
 }
 
 /// MPC handler that implements the official RMCP SDK interface
 #[derive(Clone)]
 pub struct MpcHandler<T: DatabaseService + Clone + Send + Sync + 'static> {
-    db_service: T
+    db_service: T,
+    /// Active content subscriptions registered through `subscribe_content`.
+    subscriptions: Registry,
+    /// Default page size applied to listings and search results.
+    page_size: usize,
+    /// Synonym map consulted to expand query terms, loaded from the database on
+    /// first use and invalidated when a synonym tool mutates it.
+    synonyms: Arc<tokio::sync::Mutex<Option<Synonyms>>>,
+    /// Casbin authorization layer and token store. Wired on every transport
+    /// (stdio and networked) so a tool call resolves its subject and is
+    /// enforced before touching the database; `None` only when the handler is
+    /// built without `with_authorization`.
+    authz: Option<(Authorization, TokenStore)>,
+    /// Meaning-based search backend. When present, a query parsed as `similar`
+    /// /`like` is answered by vector similarity instead of the literal
+    /// `$text`/regex paths; `None` falls back to keyword search.
+    vector_search: Option<Arc<crate::services::vector_search::VectorSearchService>>,
+    /// Formatted-result cache shared with the regex search tools. `None` skips
+    /// caching entirely, matching `authz`'s opt-in wiring.
+    cache: Option<Arc<dyn ResultCache>>,
 }
 
-impl<T> MpcHandler<T> 
-where 
-    T: DatabaseService + Clone + Send + Sync + 'static 
+impl<T> MpcHandler<T>
+where
+    T: DatabaseService + Clone + Send + Sync + 'static
 {
     pub fn new(db_service: T) -> Self {
-        MpcHandler { db_service }
+        MpcHandler {
+            db_service,
+            subscriptions: new_registry(),
+            page_size: DEFAULT_PAGE_SIZE,
+            synonyms: Arc::new(tokio::sync::Mutex::new(None)),
+            authz: None,
+            vector_search: None,
+            cache: None,
+        }
+    }
+
+    /// The cached synonym map, loading it from the database on first use. A load
+    /// failure is logged and cached as an empty map so a missing `synonyms`
+    /// collection degrades to plain search rather than failing every query.
+    async fn synonyms(&self) -> Synonyms {
+        let mut guard = self.synonyms.lock().await;
+        if guard.is_none() {
+            let loaded = match self.db_service.load_synonyms().await {
+                Ok(map) => map,
+                Err(e) => {
+                    tracing::warn!("failed to load synonyms: {}", e);
+                    Synonyms::new()
+                }
+            };
+            *guard = Some(loaded);
+        }
+        guard.clone().unwrap_or_default()
+    }
+
+    /// Drop the cached synonym map so the next query reloads it after a mutation.
+    async fn invalidate_synonyms(&self) {
+        *self.synonyms.lock().await = None;
+    }
+
+    /// Cache key for a regex search's formatted page, folding the cursor and
+    /// page size in alongside the pattern so distinct pages of the same query
+    /// don't collide on one cache entry.
+    fn page_cache_key(
+        &self,
+        collection: &str,
+        regex_pattern: &str,
+        cursor: Option<&str>,
+        page_size: usize,
+    ) -> String {
+        cache_key(
+            collection,
+            &format!("{} {} {}", regex_pattern, cursor.unwrap_or(""), page_size),
+        )
+    }
+
+    /// Look up a cached formatted page, if a [`ResultCache`] is wired.
+    async fn cached_page(&self, key: &str) -> Option<String> {
+        match &self.cache {
+            Some(cache) => cache.get(key).await,
+            None => None,
+        }
+    }
+
+    /// Store a formatted page, if a [`ResultCache`] is wired.
+    async fn cache_page(&self, key: &str, formatted: &str) {
+        if let Some(cache) = &self.cache {
+            cache.set(key, formatted).await;
+        }
+    }
+
+    /// Override the default page size used for cursor pagination.
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// Share an externally-owned subscription registry, so a sibling SSE
+    /// tailing endpoint can see the subscriptions registered through this
+    /// handler's `subscribe_content` tool.
+    pub fn with_subscriptions(mut self, subscriptions: Registry) -> Self {
+        self.subscriptions = subscriptions;
+        self
+    }
+
+    /// Attach the Casbin authorization layer and token store. With this
+    /// wired, every dispatch resolves the
+    /// presented token to a subject and enforces the tool's `(collection,
+    /// action)` before running; without it, only the JWT gate applies.
+    pub fn with_authorization(mut self, authz: Authorization, token_store: TokenStore) -> Self {
+        self.authz = Some((authz, token_store));
+        self
+    }
+
+    /// Attach the vector-search backend so queries parsed as `similar`/`like`
+    /// are answered by meaning rather than literal matching.
+    pub fn with_vector_search(
+        mut self,
+        vector_search: Arc<crate::services::vector_search::VectorSearchService>,
+    ) -> Self {
+        self.vector_search = Some(vector_search);
+        self
+    }
+
+    /// Attach the formatted-result cache consulted by the regex search tools.
+    /// Without it, every call hits the database directly.
+    pub fn with_cache(mut self, cache: Arc<dyn ResultCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// The subscription registry, shared with the SSE tailing endpoint.
+    pub fn subscriptions(&self) -> Registry {
+        self.subscriptions.clone()
+    }
+}
+
+// The single conversion from a structured [`McpError`] into an RMCP error,
+// picking the JSON-RPC code from the error's status: client faults (bad regex,
+// query, or id; 4xx) become `invalid_params` (-32602), genuine server faults
+// (5xx) become `internal_error` (-32603). Every transport routes through here
+// so error semantics stay identical across stdio, SSE, and HTTP.
+fn mcp_error_to_rmcp(mcp: McpError) -> RmcpError {
+    let body = mcp.to_response();
+    if mcp.status() < 500 {
+        RmcpError::invalid_params(mcp.message(), Some(body))
+    } else {
+        RmcpError::internal_error(mcp.message(), Some(body))
+    }
+}
+
+// Map a database-layer `anyhow` failure to an RMCP error carrying the structured
+// McpError body, so clients can branch on a stable code rather than a string.
+fn to_rmcp_error(err: anyhow::Error) -> RmcpError {
+    mcp_error_to_rmcp(McpError::from_anyhow(&err))
+}
+
+/// Build an [`ERROR_UNAUTHORIZED`] RMCP error carrying `message`, used for both
+/// a failed JWT check and a Casbin deny so clients see one 403-equivalent code.
+fn unauthorized(message: &str) -> RmcpError {
+    RmcpError::new(rmcp::model::ErrorCode(ERROR_UNAUTHORIZED), message.to_string(), None)
+}
+
+/// Parse a comma-separated environment list into trimmed, non-empty entries.
+fn env_list(var: &str) -> Vec<String> {
+    std::env::var(var)
+        .unwrap_or_default()
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Allowed JWT audiences from `MCP_JWT_AUDIENCES` (empty disables the check).
+fn allowed_audiences() -> Vec<String> {
+    env_list("MCP_JWT_AUDIENCES")
+}
+
+/// Allowed principals from `MCP_ALLOWED_IDENTITIES`/`MCP_ALLOWED_GROUPS`.
+fn allowed_principals() -> AllowedPrincipals {
+    AllowedPrincipals {
+        identities: env_list("MCP_ALLOWED_IDENTITIES"),
+        groups: env_list("MCP_ALLOWED_GROUPS"),
     }
 }
 
+/// Map a tool call to the `(collection, action)` Casbin enforces against, or
+/// `None` for tools that touch no single collection (the synonym/stats surface
+/// is modelled as its own pseudo-collection, and the fan-out meta-tools defer
+/// to the enforcement of their sub-calls).
+fn resource_for(tool_name: &str, args: &serde_json::Map<String, Value>) -> Option<(String, &'static str)> {
+    let collection = |name: &str| Some((name.to_string(), "read"));
+    match tool_name {
+        "query_database" => {
+            let c = args.get("collection").and_then(|v| v.as_str()).unwrap_or("all");
+            Some((c.to_string(), "read"))
+        }
+        "get_chapter_content" | "query_chapter_regex" | "get_similar_chapters" => collection("chapters"),
+        "get_character_details" | "query_character_regex" | "get_similar_characters" => collection("characters"),
+        "query_qa_regex" => collection("qa"),
+        "fuzzy_search" | "subscribe_content" | "get_context" => collection("all"),
+        "list_synonyms" => collection("synonyms"),
+        "get_database_stats" => collection("stats"),
+        "add_synonym" | "remove_synonym" => Some(("synonyms".to_string(), "write")),
+        "update_chapter_summary" => Some(("chapters".to_string(), "write")),
+        _ => None,
+    }
+}
+
+
+/// The declarative input schema for a tool, shared by `list_tools` (which
+/// renders it to JSON Schema) and `dispatch_tool` (which validates arguments
+/// against it). Returns `None` for tools whose arguments are free-form.
+fn schema_for(tool: &str) -> Option<ToolSchema> {
+    Some(match tool {
+        "query_database" => ToolSchema::new()
+            .string("query", "Natural language query to search the database", true)
+            .enum_str(
+                "collection",
+                "Type of data to query: novels, chapters, characters, or qa",
+                &["novels", "chapters", "characters", "qa"],
+                false,
+            )
+            .integer("limit", "Maximum entries to return in the first page", Some(1), None)
+            .string("cursor", "Opaque pagination cursor from a previous response's next_cursor", false)
+            .string("filter", "Compact filter expression, e.g. `number >= 10 AND number <= 20 AND tags IN [war, betrayal]`", false),
+        "get_chapter_content" => ToolSchema::new()
+            .string("chapter_id", "ID of the chapter to retrieve", true),
+        "get_character_details" => ToolSchema::new()
+            .string("character_id", "ID of the character to retrieve", true),
+        "query_qa_regex" => ToolSchema::new()
+            .string("regex_pattern", "Regular expression to match in Q&A entries", true)
+            .integer("limit", "Maximum entries to return in the first page", Some(1), None)
+            .string("cursor", "Opaque pagination cursor from a previous response's next_cursor", false),
+        "query_chapter_regex" => ToolSchema::new()
+            .string("regex_pattern", "Regular expression to match in chapter titles or content", true)
+            .integer("limit", "Maximum entries to return in the first page", Some(1), None)
+            .string("cursor", "Opaque pagination cursor from a previous response's next_cursor", false),
+        "query_character_regex" => ToolSchema::new()
+            .string("regex_pattern", "Regular expression to match in character names or descriptions", true)
+            .integer("limit", "Maximum entries to return in the first page", Some(1), None)
+            .string("cursor", "Opaque pagination cursor from a previous response's next_cursor", false),
+        "fuzzy_search" => ToolSchema::new()
+            .string("query", "Words to search for across titles, summaries, character names, and chapter content (typos tolerated)", true)
+            .integer("max_edits", "Optional cap on the per-word typo budget; lower tightens matching for small context windows", Some(0), None)
+            .integer("limit", "Maximum hits to return per type", Some(1), None)
+            .string("cursor", "Opaque pagination cursor from a previous response's next_cursor", false),
+        "add_synonym" => ToolSchema::new()
+            .string("term", "A name or word", true)
+            .string("alias", "An interchangeable nickname or variant", true),
+        "remove_synonym" => ToolSchema::new()
+            .string("term", "A name or word", true)
+            .string("alias", "The interchangeable variant to unlink", true),
+        "get_similar_chapters" => ToolSchema::new()
+            .string("chapter_id", "ID of the chapter to find relatives of", true)
+            .integer("limit", "Maximum neighbors to return", Some(1), None),
+        "get_similar_characters" => ToolSchema::new()
+            .string("character_id", "ID of the character to find relatives of", true)
+            .integer("limit", "Maximum neighbors to return", Some(1), None),
+        "list_synonyms" => ToolSchema::new(),
+        "get_database_stats" => ToolSchema::new(),
+        "update_chapter_summary" => ToolSchema::new()
+            .string("chapter_id", "ID of the chapter to update", true)
+            .string("summary", "Replacement summary text", true),
+        "get_context" => ToolSchema::new()
+            .string("query", "Natural language query describing the context to assemble", true)
+            .integer("max_tokens", "Token budget the assembled context must fit within", Some(1), None),
+        _ => return None,
+    })
+}
+
 // Helper to create PromptMessageContent from string
 fn create_content(text: &str) -> PromptMessageContent {
     PromptMessageContent::Text { 
@@ -194,81 +470,128 @@ fn format_chapter_content(chapter: &serde_json::Value) -> Value {
 }
 
 fn format_all_results(results: &serde_json::Value) -> Value {
-    let mut sections = Vec::new();
-    
-    if let Some(novels) = results["novels"].as_array() {
-        if !novels.is_empty() {
-            let formatted_novels = format!("NOVELS (top {} results):\n{}", 
-                novels.len().min(3),
-                novels.iter().take(3).enumerate().map(|(i, novel)| {
-                    let title = novel["title"].as_str().unwrap_or("Unknown title");
-                    let author = novel["author"].as_str().unwrap_or("Unknown author");
-                    format!("{}. {} by {}", i+1, title, author)
-                }).collect::<Vec<String>>().join("\n")
-            );
-            sections.push(formatted_novels);
+    format_all_results_page(results, 0, usize::MAX)
+}
+
+/// Render a combined `search_all` result, windowing each collection to the
+/// `[offset, offset + page_size)` slice instead of always taking the top 3, so
+/// a client can walk the full result set one cursor-sized page at a time.
+fn format_all_results_page(results: &serde_json::Value, offset: usize, page_size: usize) -> Value {
+    // Render one labelled section over the paged window of a collection.
+    fn section<F>(results: &Value, key: &str, label: &str, offset: usize, page_size: usize, line: F) -> Option<String>
+    where
+        F: Fn(usize, &Value) -> String,
+    {
+        let items = results[key].as_array()?;
+        let window: Vec<&Value> = items.iter().skip(offset).take(page_size).collect();
+        if window.is_empty() {
+            return None;
         }
+        let body = window.iter().enumerate()
+            .map(|(i, item)| line(offset + i, item))
+            .collect::<Vec<String>>()
+            .join("\n");
+        Some(format!("{} ({} of {} results):\n{}", label, window.len(), items.len(), body))
     }
-    
-    if let Some(characters) = results["characters"].as_array() {
-        if !characters.is_empty() {
-            let formatted_chars = format!("CHARACTERS (top {} results):\n{}", 
-                characters.len().min(3),
-                characters.iter().take(3).enumerate().map(|(i, char)| {
-                    let name = char["name"].as_str().unwrap_or("Unknown");
-                    let novel = char["novel_title"].as_str().unwrap_or("Unknown novel");
-                    format!("{}. {} from {}", i+1, name, novel)
-                }).collect::<Vec<String>>().join("\n")
-            );
-            sections.push(formatted_chars);
-        }
+
+    let mut sections = Vec::new();
+    if let Some(s) = section(results, "novels", "NOVELS", offset, page_size, |i, novel| {
+        let title = novel["title"].as_str().unwrap_or("Unknown title");
+        let author = novel["author"].as_str().unwrap_or("Unknown author");
+        format!("{}. {} by {}", i + 1, title, author)
+    }) {
+        sections.push(s);
     }
-    
-    if let Some(chapters) = results["chapters"].as_array() {
-        if !chapters.is_empty() {
-            let formatted_chapters = format!("CHAPTERS (top {} results):\n{}", 
-                chapters.len().min(3),
-                chapters.iter().take(3).enumerate().map(|(i, chapter)| {
-                    let title = chapter["title"].as_str().unwrap_or("Untitled");
-                    let novel = chapter["novel_title"].as_str().unwrap_or("Unknown novel");
-                    format!("{}. {} from {}", i+1, title, novel)
-                }).collect::<Vec<String>>().join("\n")
-            );
-            sections.push(formatted_chapters);
-        }
+    if let Some(s) = section(results, "characters", "CHARACTERS", offset, page_size, |i, char| {
+        let name = char["name"].as_str().unwrap_or("Unknown");
+        let novel = char["novel_title"].as_str().unwrap_or("Unknown novel");
+        format!("{}. {} from {}", i + 1, name, novel)
+    }) {
+        sections.push(s);
     }
-    
-    if let Some(qa) = results["qa"].as_array() {
-        if !qa.is_empty() {
-            let formatted_qa = format!("Q&A (top {} results):\n{}", 
-                qa.len().min(3),
-                qa.iter().take(3).enumerate().map(|(i, q)| {
-                    let question = q["question"].as_str().unwrap_or("Unknown question");
-                    format!("{}. {}", i+1, truncate_text(question, 100))
-                }).collect::<Vec<String>>().join("\n")
-            );
-            sections.push(formatted_qa);
-        }
+    if let Some(s) = section(results, "chapters", "CHAPTERS", offset, page_size, |i, chapter| {
+        let title = chapter["title"].as_str().unwrap_or("Untitled");
+        let novel = chapter["novel_title"].as_str().unwrap_or("Unknown novel");
+        format!("{}. {} from {}", i + 1, title, novel)
+    }) {
+        sections.push(s);
     }
-    
+    if let Some(s) = section(results, "qa", "Q&A", offset, page_size, |i, q| {
+        let question = q["question"].as_str().unwrap_or("Unknown question");
+        format!("{}. {}", i + 1, truncate_text(question, 100))
+    }) {
+        sections.push(s);
+    }
+
     if sections.is_empty() {
         return json!("No results found matching your query.");
     }
-    
+
     let total_count = results["novels"].as_array().map_or(0, |v| v.len()) +
                      results["characters"].as_array().map_or(0, |v| v.len()) +
                      results["chapters"].as_array().map_or(0, |v| v.len()) +
                      results["qa"].as_array().map_or(0, |v| v.len());
-    
+
     let summary = format!(
         "Found {} results matching your query. Here's a summary:\n\n{}",
         total_count,
         sections.join("\n\n")
     );
-    
+
     json!(summary)
 }
 
+/// The largest collection count in a combined `search_all` result, used to
+/// decide whether another page remains.
+fn max_collection_len(results: &serde_json::Value) -> usize {
+    ["novels", "characters", "chapters", "qa"]
+        .iter()
+        .map(|k| results[*k].as_array().map_or(0, |v| v.len()))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Resolve the window for a single-collection result set. Decodes and validates
+/// any supplied cursor against the current query, returning the slice bounds and
+/// the `next_cursor` token to advertise when more results remain.
+fn page_window(
+    total: usize,
+    collection: &str,
+    query: &str,
+    cursor_arg: Option<&str>,
+    default_page_size: usize,
+) -> Result<(usize, usize, Option<String>), RmcpError> {
+    let query_hash = Cursor::hash_query(query);
+    let (offset, page_size) = match cursor_arg {
+        Some(token) => {
+            let cursor = Cursor::decode(token, query_hash)
+                .map_err(|e| RmcpError::invalid_params(e.to_string(), None))?;
+            if cursor.collection != collection {
+                return Err(RmcpError::invalid_params("cursor is for a different collection", None));
+            }
+            (cursor.offset, cursor.page_size.max(1))
+        }
+        None => (0, default_page_size),
+    };
+
+    let end = offset.saturating_add(page_size).min(total);
+    let next = if end < total {
+        Some(Cursor::new(collection, query_hash, end, page_size).encode())
+    } else {
+        None
+    };
+    Ok((offset.min(total), end, next))
+}
+
+/// Append the `next_cursor` token to a formatted payload when a further page is
+/// available, keeping the hint inline with the token-efficient text surface.
+fn with_cursor(mut text: String, next: Option<String>) -> String {
+    if let Some(token) = next {
+        text.push_str(&format!("\n\n[next_cursor: {}]", token));
+    }
+    text
+}
+
 // Helper function to truncate text with ellipsis
 fn truncate_text(text: &str, max_length: usize) -> String {
     if text.len() <= max_length {
@@ -298,7 +621,7 @@ impl<T: DatabaseService + Clone + Send + Sync + 'static> ServerHandler for MpcHa
     /// List available prompts for this server
     async fn list_prompts(
         &self,
-        _request: rmcp::model::PaginatedRequestParam,
+        request: rmcp::model::PaginatedRequestParam,
         _context: RequestContext<RoleServer>,
     ) -> Result<ListPromptsResult, RmcpError> {
         // Define example prompts for this database service
@@ -320,9 +643,14 @@ impl<T: DatabaseService + Clone + Send + Sync + 'static> ServerHandler for MpcHa
             },
         ];
 
+        // Page the prompt list with an opaque cursor so a small-context client
+        // can walk it deterministically.
+        let (offset, end, next) = page_window(
+            prompts.len(), "prompts", "prompts", request.cursor.as_deref(), self.page_size,
+        )?;
         Ok(ListPromptsResult {
-            prompts,
-            next_cursor: None,
+            prompts: prompts[offset..end].to_vec(),
+            next_cursor: next,
         })
     }
 
@@ -376,174 +704,223 @@ impl<T: DatabaseService + Clone + Send + Sync + 'static> ServerHandler for MpcHa
     /// List available tools for this server
     async fn list_tools(
         &self, 
-        _request: rmcp::model::PaginatedRequestParam,
+        request: rmcp::model::PaginatedRequestParam,
         _context: RequestContext<RoleServer>,
     ) -> Result<rmcp::model::ListToolsResult, RmcpError> {
         use rmcp::model::{Tool, ListToolsResult};
         use std::borrow::Cow;
         
-        let tool_schema = std::sync::Arc::new(serde_json::to_value({
-            let mut schema = serde_json::Map::new();
-            schema.insert("type".to_string(), serde_json::Value::String("object".to_string()));
-            
-            let mut properties = serde_json::Map::new();
-            
-            let mut query_prop = serde_json::Map::new();
-            query_prop.insert("type".to_string(), serde_json::Value::String("string".to_string()));
-            query_prop.insert("description".to_string(), serde_json::Value::String("Natural language query to search the database".to_string()));
-            
-            let mut collection_prop = serde_json::Map::new();
-            collection_prop.insert("type".to_string(), serde_json::Value::String("string".to_string()));
-            collection_prop.insert("description".to_string(), serde_json::Value::String("Type of data to query: novels, chapters, characters, or qa".to_string()));
-            
-            let mut enum_values = serde_json::Value::Array(vec![]);
-            if let serde_json::Value::Array(ref mut arr) = enum_values {
-                arr.push(serde_json::Value::String("novels".to_string()));
-                arr.push(serde_json::Value::String("chapters".to_string()));
-                arr.push(serde_json::Value::String("characters".to_string()));
-                arr.push(serde_json::Value::String("qa".to_string()));
-            }
-            collection_prop.insert("enum".to_string(), enum_values);
-            
-            properties.insert("query".to_string(), serde_json::Value::Object(query_prop));
-            properties.insert("collection".to_string(), serde_json::Value::Object(collection_prop));
-            
-            schema.insert("properties".to_string(), serde_json::Value::Object(properties));
-            
-            let required = serde_json::Value::Array(vec![serde_json::Value::String("query".to_string())]);
-            schema.insert("required".to_string(), required);
-            
-            schema
-        }).unwrap_or_default().as_object().unwrap().clone());
-
-        // Define the database query tool
+        // Tool input schemas come from the declarative builder (see
+        // `schema_for`), which also backs argument validation in
+        // `dispatch_tool` so the two can never drift apart. The
+        // `subscribe_content`, `unsubscribe`, `run_plan` and `batch_query`
+        // schemas are built by hand below and are not validated at dispatch.
         let query_tool = Tool {
             name: Cow::from("query_database"),
             description: Cow::from("Query the database using natural language"),
-            input_schema: tool_schema,
+            input_schema: schema_for("query_database").unwrap().build(),
         };
-        
-        // Create chapter tool schema
-        let chapter_schema = std::sync::Arc::new(serde_json::to_value({
-            let mut schema = serde_json::Map::new();
-            schema.insert("type".to_string(), serde_json::Value::String("object".to_string()));
-            
-            let mut properties = serde_json::Map::new();
-            let mut chapter_id_prop = serde_json::Map::new();
-            chapter_id_prop.insert("type".to_string(), serde_json::Value::String("string".to_string()));
-            chapter_id_prop.insert("description".to_string(), serde_json::Value::String("ID of the chapter to retrieve".to_string()));
-            properties.insert("chapter_id".to_string(), serde_json::Value::Object(chapter_id_prop));
-            
-            schema.insert("properties".to_string(), serde_json::Value::Object(properties));
-            let required = serde_json::Value::Array(vec![serde_json::Value::String("chapter_id".to_string())]);
-            schema.insert("required".to_string(), required);
-            
-            schema
-        }).unwrap_or_default().as_object().unwrap().clone());
-        
-        // Define the chapter content tool
+
         let chapter_tool = Tool {
             name: Cow::from("get_chapter_content"),
             description: Cow::from("Retrieve the content of a specific chapter"),
-            input_schema: chapter_schema,
+            input_schema: schema_for("get_chapter_content").unwrap().build(),
         };
-        
-        // Define the character details tool with proper schema format
-        let character_schema = std::sync::Arc::new(serde_json::to_value({
+
+        let character_tool = Tool {
+            name: Cow::from("get_character_details"),
+            description: Cow::from("Retrieve detailed information about a character"),
+            input_schema: schema_for("get_character_details").unwrap().build(),
+        };
+
+        let regex_qa_tool = Tool {
+            name: Cow::from("query_qa_regex"),
+            description: Cow::from("Search Q&A entries using a regex pattern"),
+            input_schema: schema_for("query_qa_regex").unwrap().build(),
+        };
+
+        let regex_chapter_tool = Tool {
+            name: Cow::from("query_chapter_regex"),
+            description: Cow::from("Search chapters using a regex pattern"),
+            input_schema: schema_for("query_chapter_regex").unwrap().build(),
+        };
+
+        let regex_character_tool = Tool {
+            name: Cow::from("query_character_regex"),
+            description: Cow::from("Search characters using a regex pattern"),
+            input_schema: schema_for("query_character_regex").unwrap().build(),
+        };
+
+        let fuzzy_tool = Tool {
+            name: Cow::from("fuzzy_search"),
+            description: Cow::from("Typo-tolerant ranked search over novels, characters, and chapters"),
+            input_schema: schema_for("fuzzy_search").unwrap().build(),
+        };
+
+        // Create subscribe tool schema: an open filter object plus the optional
+        // backfill limit carried inside it.
+        let subscribe_schema = std::sync::Arc::new(serde_json::to_value({
             let mut schema = serde_json::Map::new();
             schema.insert("type".to_string(), serde_json::Value::String("object".to_string()));
-            
+
             let mut properties = serde_json::Map::new();
-            let mut character_id_prop = serde_json::Map::new();
-            character_id_prop.insert("type".to_string(), serde_json::Value::String("string".to_string()));
-            character_id_prop.insert("description".to_string(), serde_json::Value::String("ID of the character to retrieve".to_string()));
-            properties.insert("character_id".to_string(), serde_json::Value::Object(character_id_prop));
-            
+            let mut filter_prop = serde_json::Map::new();
+            filter_prop.insert("type".to_string(), serde_json::Value::String("object".to_string()));
+            filter_prop.insert("description".to_string(), serde_json::Value::String("Declarative filter: optional ids, collections, authors, since, until, limit, tags".to_string()));
+            properties.insert("filter".to_string(), serde_json::Value::Object(filter_prop));
+
             schema.insert("properties".to_string(), serde_json::Value::Object(properties));
-            let required = serde_json::Value::Array(vec![serde_json::Value::String("character_id".to_string())]);
-            schema.insert("required".to_string(), required);
-            
+            schema.insert("required".to_string(), serde_json::Value::Array(vec![]));
+
             schema
         }).unwrap_or_default().as_object().unwrap().clone());
-        
-        let character_tool = Tool {
-            name: Cow::from("get_character_details"),
-            description: Cow::from("Retrieve detailed information about a character"),
-            input_schema: character_schema,
+
+        let subscribe_tool = Tool {
+            name: Cow::from("subscribe_content"),
+            description: Cow::from("Subscribe to new or updated documents matching a filter; returns a subscription id and an initial backfill"),
+            input_schema: subscribe_schema,
         };
-        
-        // Create regex QA tool schema
-        let regex_qa_schema = std::sync::Arc::new(serde_json::to_value({
+
+        // Create unsubscribe tool schema.
+        let unsubscribe_schema = std::sync::Arc::new(serde_json::to_value({
             let mut schema = serde_json::Map::new();
             schema.insert("type".to_string(), serde_json::Value::String("object".to_string()));
-            
+
             let mut properties = serde_json::Map::new();
-            let mut regex_prop = serde_json::Map::new();
-            regex_prop.insert("type".to_string(), serde_json::Value::String("string".to_string()));
-            regex_prop.insert("description".to_string(), serde_json::Value::String("Regular expression to match in Q&A entries".to_string()));
-            properties.insert("regex_pattern".to_string(), serde_json::Value::Object(regex_prop));
-            
+            let mut id_prop = serde_json::Map::new();
+            id_prop.insert("type".to_string(), serde_json::Value::String("string".to_string()));
+            id_prop.insert("description".to_string(), serde_json::Value::String("Subscription id returned by subscribe_content".to_string()));
+            properties.insert("subscription_id".to_string(), serde_json::Value::Object(id_prop));
+
             schema.insert("properties".to_string(), serde_json::Value::Object(properties));
-            let required = serde_json::Value::Array(vec![serde_json::Value::String("regex_pattern".to_string())]);
-            schema.insert("required".to_string(), required);
-            
+            schema.insert("required".to_string(), serde_json::Value::Array(vec![serde_json::Value::String("subscription_id".to_string())]));
+
             schema
         }).unwrap_or_default().as_object().unwrap().clone());
-        
-        let regex_qa_tool = Tool {
-            name: Cow::from("query_qa_regex"),
-            description: Cow::from("Search Q&A entries using a regex pattern"),
-            input_schema: regex_qa_schema,
+
+        let unsubscribe_tool = Tool {
+            name: Cow::from("unsubscribe"),
+            description: Cow::from("Drop a content subscription by id"),
+            input_schema: unsubscribe_schema,
         };
-        
-        // Create chapter regex tool schema
-        let regex_chapter_schema = std::sync::Arc::new(serde_json::to_value({
+
+        // Multi-step plan: an ordered list of tool invocations whose `args` may
+        // reference earlier bound results via `${bind.path}` placeholders.
+        let run_plan_schema = std::sync::Arc::new(serde_json::to_value({
             let mut schema = serde_json::Map::new();
             schema.insert("type".to_string(), serde_json::Value::String("object".to_string()));
-            
+
+            let mut step_props = serde_json::Map::new();
+            step_props.insert("tool".to_string(), json!({"type": "string", "description": "Name of the tool to invoke"}));
+            step_props.insert("args".to_string(), json!({"type": "object", "description": "Arguments; values may contain ${bind.path} references to earlier steps"}));
+            step_props.insert("bind".to_string(), json!({"type": "string", "description": "Optional name to store this step's result under"}));
+            let step_schema = json!({
+                "type": "object",
+                "properties": step_props,
+                "required": ["tool"],
+            });
+
             let mut properties = serde_json::Map::new();
-            let mut regex_prop = serde_json::Map::new();
-            regex_prop.insert("type".to_string(), serde_json::Value::String("string".to_string()));
-            regex_prop.insert("description".to_string(), serde_json::Value::String("Regular expression to match in chapter titles or content".to_string()));
-            properties.insert("regex_pattern".to_string(), serde_json::Value::Object(regex_prop));
-            
+            properties.insert("steps".to_string(), json!({
+                "type": "array",
+                "description": "Ordered steps executed sequentially; later steps can reference earlier results",
+                "items": step_schema,
+            }));
+
             schema.insert("properties".to_string(), serde_json::Value::Object(properties));
-            let required = serde_json::Value::Array(vec![serde_json::Value::String("regex_pattern".to_string())]);
-            schema.insert("required".to_string(), required);
-            
+            schema.insert("required".to_string(), serde_json::Value::Array(vec![serde_json::Value::String("steps".to_string())]));
+
             schema
         }).unwrap_or_default().as_object().unwrap().clone());
-        
-        let regex_chapter_tool = Tool {
-            name: Cow::from("query_chapter_regex"),
-            description: Cow::from("Search chapters using a regex pattern"),
-            input_schema: regex_chapter_schema,
+
+        let run_plan_tool = Tool {
+            name: Cow::from("run_plan"),
+            description: Cow::from("Execute an ordered list of tool steps in one request, threading earlier results into later step arguments via ${bind.path} placeholders"),
+            input_schema: run_plan_schema,
         };
-        
-        // Create character regex tool schema
-        let regex_character_schema = std::sync::Arc::new(serde_json::to_value({
+
+        // Batch: an array of independent tool invocations executed concurrently,
+        // each returning its own result or error slot in input order.
+        let batch_query_schema = std::sync::Arc::new(serde_json::to_value({
             let mut schema = serde_json::Map::new();
             schema.insert("type".to_string(), serde_json::Value::String("object".to_string()));
-            
+
+            let mut op_props = serde_json::Map::new();
+            op_props.insert("tool".to_string(), json!({"type": "string", "description": "Name of the tool to invoke"}));
+            op_props.insert("args".to_string(), json!({"type": "object", "description": "Arguments passed to the tool"}));
+            let op_schema = json!({
+                "type": "object",
+                "properties": op_props,
+                "required": ["tool"],
+            });
+
             let mut properties = serde_json::Map::new();
-            let mut regex_prop = serde_json::Map::new();
-            regex_prop.insert("type".to_string(), serde_json::Value::String("string".to_string()));
-            regex_prop.insert("description".to_string(), serde_json::Value::String("Regular expression to match in character names or descriptions".to_string()));
-            properties.insert("regex_pattern".to_string(), serde_json::Value::Object(regex_prop));
-            
+            properties.insert("operations".to_string(), json!({
+                "type": "array",
+                "description": "Independent operations executed concurrently; results are returned in the same order",
+                "items": op_schema,
+            }));
+
             schema.insert("properties".to_string(), serde_json::Value::Object(properties));
-            let required = serde_json::Value::Array(vec![serde_json::Value::String("regex_pattern".to_string())]);
-            schema.insert("required".to_string(), required);
-            
+            schema.insert("required".to_string(), serde_json::Value::Array(vec![serde_json::Value::String("operations".to_string())]));
+
             schema
         }).unwrap_or_default().as_object().unwrap().clone());
-        
-        let regex_character_tool = Tool {
-            name: Cow::from("query_character_regex"),
-            description: Cow::from("Search characters using a regex pattern"),
-            input_schema: regex_character_schema,
+
+        let batch_query_tool = Tool {
+            name: Cow::from("batch_query"),
+            description: Cow::from("Run several read tools concurrently in one request, returning an ordered array of per-operation results"),
+            input_schema: batch_query_schema,
         };
-        
+
+        let add_synonym_tool = Tool {
+            name: Cow::from("add_synonym"),
+            description: Cow::from("Teach the server that two names are interchangeable so queries find either"),
+            input_schema: schema_for("add_synonym").unwrap().build(),
+        };
+
+        let remove_synonym_tool = Tool {
+            name: Cow::from("remove_synonym"),
+            description: Cow::from("Unlink a previously added synonym pair"),
+            input_schema: schema_for("remove_synonym").unwrap().build(),
+        };
+
+        let list_synonyms_tool = Tool {
+            name: Cow::from("list_synonyms"),
+            description: Cow::from("List every known synonym pair"),
+            input_schema: schema_for("list_synonyms").unwrap().build(),
+        };
+
+        let get_database_stats_tool = Tool {
+            name: Cow::from("get_database_stats"),
+            description: Cow::from("Report per-collection counts, chapter length totals, storage size, and freshness"),
+            input_schema: schema_for("get_database_stats").unwrap().build(),
+        };
+
+        let similar_chapters_tool = Tool {
+            name: Cow::from("get_similar_chapters"),
+            description: Cow::from("Given a chapter id, return the nearest chapters by embedding similarity, each with its score"),
+            input_schema: schema_for("get_similar_chapters").unwrap().build(),
+        };
+
+        let similar_characters_tool = Tool {
+            name: Cow::from("get_similar_characters"),
+            description: Cow::from("Given a character id, return the nearest characters by embedding similarity, each with its score"),
+            input_schema: schema_for("get_similar_characters").unwrap().build(),
+        };
+
+        let update_chapter_summary_tool = Tool {
+            name: Cow::from("update_chapter_summary"),
+            description: Cow::from("Replace a chapter's summary"),
+            input_schema: schema_for("update_chapter_summary").unwrap().build(),
+        };
+
+        let get_context_tool = Tool {
+            name: Cow::from("get_context"),
+            description: Cow::from("Assemble a ranked, token-budgeted context block from the most relevant novels, chapters, characters, and Q&A for a query"),
+            input_schema: schema_for("get_context").unwrap().build(),
+        };
+
         // Create the list of tools
         let tools = vec![
             query_tool,
@@ -552,11 +929,28 @@ impl<T: DatabaseService + Clone + Send + Sync + 'static> ServerHandler for MpcHa
             regex_qa_tool,
             regex_chapter_tool,
             regex_character_tool,
+            fuzzy_tool,
+            subscribe_tool,
+            unsubscribe_tool,
+            run_plan_tool,
+            batch_query_tool,
+            add_synonym_tool,
+            remove_synonym_tool,
+            list_synonyms_tool,
+            get_database_stats_tool,
+            similar_chapters_tool,
+            similar_characters_tool,
+            update_chapter_summary_tool,
+            get_context_tool,
         ];
         
+        // Page the tool list with an opaque cursor, matching the search tools.
+        let (offset, end, next) = page_window(
+            tools.len(), "tools", "tools", request.cursor.as_deref(), self.page_size,
+        )?;
         Ok(ListToolsResult {
-            tools,
-            next_cursor: None,
+            tools: tools[offset..end].to_vec(),
+            next_cursor: next,
         })
     }
 
@@ -568,21 +962,294 @@ impl<T: DatabaseService + Clone + Send + Sync + 'static> ServerHandler for MpcHa
     ) -> Result<CallToolResult, RmcpError> {
         let tool_name = request.name.as_ref();
         let args = request.arguments.unwrap_or_default();
-        
+        self.dispatch_tool(tool_name, args).await
+    }
+}
+
+impl<T: DatabaseService + Clone + Send + Sync + 'static> MpcHandler<T> {
+    /// The caller-requested first-page size (`limit`), falling back to the
+    /// handler default. On a continuation the cursor carries its own page size,
+    /// so this only governs the initial page.
+    fn requested_limit(&self, args: &serde_json::Map<String, Value>) -> usize {
+        args.get("limit")
+            .and_then(|v| v.as_u64())
+            .map(|v| (v as usize).max(1))
+            .unwrap_or(self.page_size)
+    }
+
+    /// Static description of what this server can do, returned by the
+    /// `query`/`q = "config"` sub-query. Lists the queryable collections, the
+    /// `query_type` values and `SearchFilters` fields the [`QueryParser`]
+    /// understands, pagination limits, whether auth is enforced, and the
+    /// supported content encodings, so an agent can configure itself instead of
+    /// guessing field names.
+    fn capabilities(&self) -> Value {
+        json!({
+            "collections": ["novels", "chapters", "characters", "qa"],
+            "query_types": ["search", "list", "summary", "details", "similar"],
+            "search_filters": [
+                "novel_id", "character_name", "tags", "ids",
+                "number_gte", "number_lte", "since", "until", "not_tags",
+            ],
+            "pagination": {
+                "default_page_size": self.page_size,
+                "cursor": "opaque, returned as next_cursor",
+            },
+            "auth_required": std::env::var("MCP_ENABLE_AUTH")
+                .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+                .unwrap_or(false),
+            "content_encodings": ["gzip"],
+        })
+    }
+
+    /// Emit `content` as a tool result, compressing it when the caller
+    /// advertised support via `accept_encoding` and the body is large enough.
+    /// A compressed body is wrapped in a small JSON envelope carrying the
+    /// `content_encoding` marker and the pre/post-compression sizes so the
+    /// client can decode it and see how much the payload was trimmed.
+    fn emit_content(&self, args: &serde_json::Map<String, Value>, content: String) -> CallToolResult {
+        self.emit_content_with_metadata(args, content, None)
+    }
+
+    /// [`emit_content`](Self::emit_content), additionally merging `metadata`
+    /// (e.g. [`ResponseMetadata`]-shaped `has_more`/`next_page_token`/
+    /// `remaining_tokens`) into the envelope so callers get structured fields
+    /// instead of markers appended to the text. Forces the JSON envelope even
+    /// when the body wasn't compressed, since there is now metadata to carry.
+    fn emit_content_with_metadata(
+        &self,
+        args: &serde_json::Map<String, Value>,
+        content: String,
+        metadata: Option<Value>,
+    ) -> CallToolResult {
+        let accept = compression::accepted_encodings(&Value::Object(args.clone()));
+        let encoded = compression::encode(&content, &accept, compression::DEFAULT_THRESHOLD);
+        let mut meta = match &encoded.encoding {
+            Some(_) => json!({
+                "uncompressed_bytes": encoded.uncompressed_bytes,
+                "compressed_bytes": encoded.compressed_bytes,
+            }),
+            None => json!({}),
+        };
+        if let Some(extra) = metadata {
+            if let (Some(meta_obj), Some(extra_obj)) = (meta.as_object_mut(), extra.as_object()) {
+                meta_obj.extend(extra_obj.clone());
+            }
+        }
+        let body = if encoded.encoding.is_some() || meta.as_object().is_some_and(|o| !o.is_empty()) {
+            json!({
+                "content_encoding": encoded.encoding,
+                "data": encoded.body,
+                "metadata": meta,
+            })
+            .to_string()
+        } else {
+            encoded.body
+        };
+        CallToolResult {
+            content: vec![Content::from_raw(body)],
+            is_error: Some(false),
+        }
+    }
+
+    /// Authorize a tool call at the single served dispatch point: validate the
+    /// presented JWT (when `MCP_ENABLE_AUTH` is on), resolve the token to its
+    /// subject and scopes through the [`TokenStore`], check the token's own
+    /// `allowed_collections`/`access`/`allowed_novel_ids` scope, then `enforce`
+    /// the subject against the tool's `(collection, action)` with Casbin. A
+    /// failed JWT check, a scope mismatch, or a Casbin deny is returned as an
+    /// [`ERROR_UNAUTHORIZED`] error so networked clients get a 403-equivalent
+    /// instead of silent access.
+    async fn authorize(
+        &self,
+        tool_name: &str,
+        args: &serde_json::Map<String, Value>,
+    ) -> Result<(), RmcpError> {
+        // JWT gate. Honours `MCP_ENABLE_AUTH` internally: when auth is off this
+        // returns an anonymous identity and never rejects.
+        let options: HashMap<String, Value> = args.clone().into_iter().collect();
+        validate_auth_token(&options, &allowed_audiences(), &allowed_principals())
+            .map_err(|e| unauthorized(e.message()))?;
+
+        // Scope and Casbin enforcement, only when `with_authorization` wired it.
+        // Resolve the presented `auth_token` through the token store; an
+        // unresolved token (unknown, revoked, or expired) is denied outright —
+        // it never falls back to trusting the raw presented value as an
+        // identity, since that would let a caller grant itself any role just
+        // by naming it. Meta-tools that fan out to other tools (`batch_query`,
+        // `run_plan`) map to no resource and are enforced per sub-call instead.
+        if let Some((authz, token_store)) = &self.authz {
+            let Some((object, action)) = resource_for(tool_name, args) else {
+                return Ok(());
+            };
+            let presented = args.get("auth_token").and_then(|v| v.as_str()).unwrap_or("");
+            let resolved = match token_store.resolve(presented).await {
+                Ok(Some(resolved)) => resolved,
+                Ok(None) => return Err(unauthorized("Access denied: unknown or expired token")),
+                Err(e) => {
+                    return Err(unauthorized(&format!("Authorization error: {}", e)))
+                }
+            };
+            // The `all` pseudo-resource (multi-collection search, fuzzy search,
+            // content subscriptions) carries no policy line of its own; require
+            // the subject to hold the action on every base collection it fans
+            // out across. Single-collection objects enforce directly.
+            let base = object.as_str();
+            let objects: &[&str] = if object == "all" {
+                &["novels", "chapters", "characters", "qa"]
+            } else {
+                std::slice::from_ref(&base)
+            };
+            for obj in objects {
+                if !resolved.permits(obj, action) {
+                    return Err(unauthorized(&format!(
+                        "Access denied: token scope does not permit '{}' on '{}'",
+                        action, obj
+                    )));
+                }
+                match authz.enforce(&resolved.subject, obj, action).await {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        return Err(unauthorized(&format!(
+                            "Access denied: '{}' may not {} '{}'",
+                            resolved.subject, action, obj
+                        )));
+                    }
+                    Err(e) => return Err(unauthorized(&format!("Authorization error: {}", e))),
+                }
+            }
+            if let Some(novel_id) = args.get("novel_id").and_then(|v| v.as_str()) {
+                if !resolved.permits_novel(novel_id) {
+                    return Err(unauthorized("Access denied: token scope does not include this novel"));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Execute a single tool by name. Shared by the [`ServerHandler::call_tool`]
+    /// entry point and by the `run_plan` step executor, which drives a sequence
+    /// of these with placeholder substitution between steps.
+    async fn dispatch_tool(
+        &self,
+        tool_name: &str,
+        mut args: serde_json::Map<String, Value>,
+    ) -> Result<CallToolResult, RmcpError> {
+        // `q = "config"` is a capabilities sub-query on `query_database`: answer
+        // what the server understands without touching any novel data. It runs
+        // ahead of schema validation (which marks `query` required) and of
+        // authorization (it reads no collection), letting an unauthenticated
+        // client self-configure its tool schema up front.
+        if tool_name == "query_database"
+            && args.get("q").and_then(|v| v.as_str()) == Some("config")
+        {
+            return Ok(CallToolResult {
+                content: vec![Content::from_raw(self.capabilities().to_string())],
+                is_error: Some(false),
+            });
+        }
+
+        // Validate and coerce arguments against the declarative schema before
+        // dispatch, so missing/enum/range violations surface as invalid_params
+        // rather than silent default fallbacks.
+        if let Some(schema) = schema_for(tool_name) {
+            schema.validate(&mut args)
+                .map_err(|e| RmcpError::invalid_params(e, None))?;
+        }
+
+        // Resolve and enforce the caller's authorization before any tool runs.
+        self.authorize(tool_name, &args).await?;
+
         // Dispatch to the appropriate handler based on tool name
         match tool_name {
             "query_database" => {
                 let query = args.get("query")
                     .and_then(|v| v.as_str())
                     .ok_or_else(|| RmcpError::invalid_params("Missing 'query' parameter", None))?;
-                
+
                 let collection = args.get("collection")
                     .and_then(|v| v.as_str())
                     .unwrap_or("all");
-                
-                // Parse the natural language query into search parameters
-                let search_params = QueryParser::parse_natural_language_query(query);
-                
+                let cursor_arg = args.get("cursor").and_then(|v| v.as_str());
+                let page_size = self.requested_limit(&args);
+
+                // Parse the natural language query into search parameters, then
+                // expand its keywords with any known synonyms so a nickname also
+                // matches the canonical name.
+                let mut search_params = QueryParser::parse_natural_language_query(query);
+
+                // Carry a compact filter expression through to the search
+                // methods, which merge it via `filter_dsl::parse_filter`.
+                if let Some(expr) = args.get("filter").and_then(|v| v.as_str()) {
+                    if !expr.trim().is_empty() {
+                        search_params.filter_expr = Some(expr.to_string());
+                    }
+                }
+
+                // Compile the comparison clauses of the natural-language query
+                // into a validated filter. An unknown field fails the call with
+                // invalid_params rather than silently matching nothing.
+                search_params.compiled_filter = QueryParser::build_filter(collection, query)
+                    .map_err(|e| RmcpError::invalid_params(e.to_string(), None))?;
+
+                let synonyms = self.synonyms().await;
+                if !synonyms.is_empty() {
+                    search_params.keywords =
+                        QueryParser::expand_synonyms(&search_params.keywords, &synonyms);
+                }
+
+                // Meaning-based retrieval: a `similar`/`like` query over a
+                // concrete collection is answered by the vector backend when one
+                // is wired, returning the nearest documents by embedding
+                // similarity instead of literal `$text`/regex matches.
+                if QueryParser::wants_semantic_search(&search_params.query_type)
+                    && matches!(collection, "novels" | "chapters" | "characters" | "qa")
+                {
+                    if let Some(vector) = &self.vector_search {
+                        let hits = vector
+                            .semantic_search(collection, query, page_size)
+                            .await
+                            .map_err(to_rmcp_error)?;
+                        let mut page: Vec<Value> = hits
+                            .into_iter()
+                            .map(|hit| {
+                                let mut value =
+                                    serde_json::to_value(&hit.document).unwrap_or(Value::Null);
+                                if let Some(object) = value.as_object_mut() {
+                                    object.insert("score".to_string(), json!(hit.score));
+                                }
+                                value
+                            })
+                            .collect();
+
+                        let mut budget_report = None;
+                        if let Some(max_tokens) = args.get("max_tokens").and_then(|v| v.as_u64()) {
+                            let estimator =
+                                budget::estimator_for(args.get("model").and_then(|v| v.as_str()));
+                            budget_report = Some(budget::fit_to_budget(
+                                &mut page,
+                                collection,
+                                max_tokens as u32,
+                                estimator.as_ref(),
+                            ));
+                        }
+
+                        let content_str = match collection {
+                            "novels" => format_novels(&page),
+                            "chapters" => format_chapters(&page),
+                            "characters" => format_characters(&page),
+                            _ => format_qa(&page),
+                        };
+
+                        let metadata = budget_report.map(|report| json!({
+                            "has_more": report.has_more,
+                            "next_page_token": Value::Null,
+                            "remaining_tokens": report.remaining,
+                        }));
+                        return Ok(self.emit_content_with_metadata(&args, content_str.to_string(), metadata));
+                    }
+                }
+
                 // Search the database with the parsed parameters
                 let result = match collection {
                     "novels" => self.db_service.search_novels(&search_params).await,
@@ -591,44 +1258,61 @@ impl<T: DatabaseService + Clone + Send + Sync + 'static> ServerHandler for MpcHa
                     "qa" => self.db_service.search_qa(&search_params).await,
                     _ => {
                         let result = self.db_service.search_all(&search_params).await
-                            .map_err(|e| RmcpError::internal_error(format!("Database error: {}", e), None))?;
-                        
-                        // Convert from Value to MCPResponse
-                        let content = format_all_results(&result);
-                        return Ok(CallToolResult {
-                            content: vec![Content::from_raw(content.to_string())],
-                            is_error: Some(false),
-                        });
+                            .map_err(to_rmcp_error)?;
+
+                        // Page the combined result across every collection.
+                        let total = max_collection_len(&result);
+                        let (offset, end, next) = page_window(total, "all", query, cursor_arg, page_size)?;
+                        let content = format_all_results_page(&result, offset, end - offset);
+                        return Ok(self.emit_content(&args, with_cursor(content.to_string(), next)));
                     },
-                }.map_err(|e| RmcpError::internal_error(format!("Database error: {}", e), None))?;
-                
-                // Format the result in a token-efficient manner
+                }.map_err(to_rmcp_error)?;
+
+                // Window the single-collection result to the requested page.
+                let data = result.data.as_array().cloned().unwrap_or_default();
+                let (offset, end, next) = page_window(data.len(), collection, query, cursor_arg, page_size)?;
+                let mut page = data[offset..end].to_vec();
+
+                // Fit the page to the caller's token budget, degrading the
+                // documents (dropping chapter content, trimming character
+                // relationships, then paginating) before formatting.
+                let mut budget_report = None;
+                if let Some(max_tokens) = args.get("max_tokens").and_then(|v| v.as_u64()) {
+                    let estimator = budget::estimator_for(args.get("model").and_then(|v| v.as_str()));
+                    budget_report = Some(budget::fit_to_budget(&mut page, collection, max_tokens as u32, estimator.as_ref()));
+                }
+
                 let content_str = match collection {
-                    "novels" => format_novels(result.data.as_array().unwrap()),
-                    "chapters" => format_chapters(result.data.as_array().unwrap()),
-                    "characters" => format_characters(result.data.as_array().unwrap()),
-                    "qa" => format_qa(result.data.as_array().unwrap()),
+                    "novels" => format_novels(&page),
+                    "chapters" => format_chapters(&page),
+                    "characters" => format_characters(&page),
+                    "qa" => format_qa(&page),
                     _ => format_all_results(&result.data),
                 };
-                
-                Ok(CallToolResult {
-                    content: vec![Content::from_raw(content_str.to_string())],
-                    is_error: Some(false),
-                })
+
+                // Surface `has_more`/`remaining_tokens` as structured metadata
+                // rather than text markers; `has_more` is true when either the
+                // keyset window paged or the budget truncated the page, so the
+                // caller knows to re-query with a cursor, a smaller page, or a
+                // larger budget for the remainder.
+                let budget_truncated = budget_report.as_ref().is_some_and(|r| r.has_more);
+                let metadata = json!({
+                    "has_more": next.is_some() || budget_truncated,
+                    "next_page_token": next,
+                    "remaining_tokens": budget_report.map(|r| r.remaining),
+                });
+                Ok(self.emit_content_with_metadata(&args, content_str.to_string(), Some(metadata)))
             },
             "get_chapter_content" => {
                 let chapter_id = args.get("chapter_id")
                     .and_then(|v| v.as_str())
                     .ok_or_else(|| RmcpError::invalid_params("Missing 'chapter_id' parameter", None))?;
-                
+
                 // Get chapter content from database
                 let result = self.db_service.get_chapter_content(chapter_id).await
-                    .map_err(|e| RmcpError::internal_error(format!("Database error: {}", e), None))?;
-                
-                Ok(CallToolResult {
-                    content: vec![Content::from_raw(result.unwrap())],
-                    is_error: Some(false),
-                })
+                    .map_err(to_rmcp_error)?;
+
+                Ok(self.emit_content(&args, result.unwrap_or_default()))
             },
             "get_character_details" => {
                 let character_id = args.get("character_id")
@@ -637,7 +1321,7 @@ impl<T: DatabaseService + Clone + Send + Sync + 'static> ServerHandler for MpcHa
                 
                 // Get character details from database
                 let result = self.db_service.get_character_details(character_id).await
-                    .map_err(|e| RmcpError::internal_error(format!("Database error: {}", e), None))?;
+                    .map_err(to_rmcp_error)?;
                 
                 // Format the character details
                 if let Some(character) = result {
@@ -661,16 +1345,30 @@ impl<T: DatabaseService + Clone + Send + Sync + 'static> ServerHandler for MpcHa
                 let regex_pattern = args.get("regex_pattern")
                     .and_then(|v| v.as_str())
                     .ok_or_else(|| RmcpError::invalid_params("Missing 'regex_pattern' parameter", None))?;
-                
-                // Search Q&A entries with regex
+
+                // Reject a malformed pattern up front as invalid_params rather
+                // than letting it surface as an opaque database failure.
+                regex::Regex::new(regex_pattern).map_err(|e| mcp_error_to_rmcp(McpError::from(e)))?;
+
+                let page_size = self.requested_limit(&args);
+                let cursor_arg = args.get("cursor").and_then(|v| v.as_str());
+                let cache_key = self.page_cache_key("qa", regex_pattern, cursor_arg, page_size);
+                if let Some(cached) = self.cached_page(&cache_key).await {
+                    return Ok(CallToolResult {
+                        content: vec![Content::from_raw(cached)],
+                        is_error: Some(false),
+                    });
+                }
+
+                // Search Q&A entries with regex, then window to the requested page.
                 let result = self.db_service.search_qa_by_regex(regex_pattern).await
-                    .map_err(|e| RmcpError::internal_error(format!("Database error: {}", e), None))?;
-                
-                // Format the results
-                let formatted = format_qa(&result);
-                
+                    .map_err(to_rmcp_error)?;
+                let (offset, end, next) = page_window(result.len(), "qa", regex_pattern, cursor_arg, page_size)?;
+                let formatted = with_cursor(format_qa(&result[offset..end]).to_string(), next);
+                self.cache_page(&cache_key, &formatted).await;
+
                 Ok(CallToolResult {
-                    content: vec![Content::from_raw(formatted.to_string())],
+                    content: vec![Content::from_raw(formatted)],
                     is_error: Some(false),
                 })
             },
@@ -678,16 +1376,30 @@ impl<T: DatabaseService + Clone + Send + Sync + 'static> ServerHandler for MpcHa
                 let regex_pattern = args.get("regex_pattern")
                     .and_then(|v| v.as_str())
                     .ok_or_else(|| RmcpError::invalid_params("Missing 'regex_pattern' parameter", None))?;
-                
-                // Search chapters with regex
+
+                // Reject a malformed pattern up front as invalid_params rather
+                // than letting it surface as an opaque database failure.
+                regex::Regex::new(regex_pattern).map_err(|e| mcp_error_to_rmcp(McpError::from(e)))?;
+
+                let page_size = self.requested_limit(&args);
+                let cursor_arg = args.get("cursor").and_then(|v| v.as_str());
+                let cache_key = self.page_cache_key("chapters", regex_pattern, cursor_arg, page_size);
+                if let Some(cached) = self.cached_page(&cache_key).await {
+                    return Ok(CallToolResult {
+                        content: vec![Content::from_raw(cached)],
+                        is_error: Some(false),
+                    });
+                }
+
+                // Search chapters with regex, then window to the requested page.
                 let result = self.db_service.search_chapters_by_regex(regex_pattern).await
-                    .map_err(|e| RmcpError::internal_error(format!("Database error: {}", e), None))?;
-                
-                // Format the results
-                let formatted = format_chapters(&result);
-                
+                    .map_err(to_rmcp_error)?;
+                let (offset, end, next) = page_window(result.len(), "chapters", regex_pattern, cursor_arg, page_size)?;
+                let formatted = with_cursor(format_chapters(&result[offset..end]).to_string(), next);
+                self.cache_page(&cache_key, &formatted).await;
+
                 Ok(CallToolResult {
-                    content: vec![Content::from_raw(formatted.to_string())],
+                    content: vec![Content::from_raw(formatted)],
                     is_error: Some(false),
                 })
             },
@@ -695,16 +1407,319 @@ impl<T: DatabaseService + Clone + Send + Sync + 'static> ServerHandler for MpcHa
                 let regex_pattern = args.get("regex_pattern")
                     .and_then(|v| v.as_str())
                     .ok_or_else(|| RmcpError::invalid_params("Missing 'regex_pattern' parameter", None))?;
-                
-                // Search characters with regex
+
+                // Reject a malformed pattern up front as invalid_params rather
+                // than letting it surface as an opaque database failure.
+                regex::Regex::new(regex_pattern).map_err(|e| mcp_error_to_rmcp(McpError::from(e)))?;
+
+                let page_size = self.requested_limit(&args);
+                let cursor_arg = args.get("cursor").and_then(|v| v.as_str());
+                let cache_key = self.page_cache_key("characters", regex_pattern, cursor_arg, page_size);
+                if let Some(cached) = self.cached_page(&cache_key).await {
+                    return Ok(CallToolResult {
+                        content: vec![Content::from_raw(cached)],
+                        is_error: Some(false),
+                    });
+                }
+
+                // Search characters with regex, then window to the requested page.
                 let result = self.db_service.search_characters_by_regex(regex_pattern).await
-                    .map_err(|e| RmcpError::internal_error(format!("Database error: {}", e), None))?;
-                
-                // Format the results
-                let formatted = format_characters(&result);
-                
+                    .map_err(to_rmcp_error)?;
+                let (offset, end, next) = page_window(result.len(), "characters", regex_pattern, cursor_arg, page_size)?;
+                let formatted = with_cursor(format_characters(&result[offset..end]).to_string(), next);
+                self.cache_page(&cache_key, &formatted).await;
+
                 Ok(CallToolResult {
-                    content: vec![Content::from_raw(formatted.to_string())],
+                    content: vec![Content::from_raw(formatted)],
+                    is_error: Some(false),
+                })
+            },
+            "fuzzy_search" => {
+                let query = args.get("query")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| RmcpError::invalid_params("Missing 'query' parameter", None))?;
+
+                let max_edits = args.get("max_edits")
+                    .and_then(|v| v.as_u64())
+                    .map(|v| v as usize);
+                let limit = args.get("limit")
+                    .and_then(|v| v.as_u64())
+                    .map(|v| v as usize)
+                    .unwrap_or(3);
+
+                let cursor_arg = args.get("cursor").and_then(|v| v.as_str());
+
+                // Ranked, bucketed results feed the existing summary formatter,
+                // windowed to the requested page.
+                let result = self.db_service.fuzzy_search(query, max_edits, limit).await
+                    .map_err(to_rmcp_error)?;
+                let total = max_collection_len(&result);
+                let (offset, end, next) = page_window(total, "fuzzy", query, cursor_arg, self.page_size)?;
+                let content = format_all_results_page(&result, offset, end - offset);
+
+                Ok(CallToolResult {
+                    content: vec![Content::from_raw(with_cursor(content.to_string(), next))],
+                    is_error: Some(false),
+                })
+            },
+            "subscribe_content" => {
+                // A bad filter deserializes into a match-nothing filter rather
+                // than failing the call, matching ReqFilter's contract.
+                let filter_value = args.get("filter")
+                    .cloned()
+                    .unwrap_or_else(|| Value::Object(serde_json::Map::new()));
+                let filter = ReqFilter::from_value(filter_value);
+
+                // Bounded initial backfill over every collection, reusing the
+                // combined-results formatter for the payload.
+                let combined = self.db_service.search_all(&broad_search_params()).await
+                    .map_err(to_rmcp_error)?;
+                let backfill = collect_matches(&filter, &combined);
+                let content = format_all_results(&backfill);
+
+                // Register the live subscription under a fresh id.
+                let id = Uuid::new_v4().to_string();
+                self.subscriptions.lock().await.insert(id.clone(), filter);
+
+                let payload = json!({
+                    "subscription_id": id,
+                    "backfill": content,
+                });
+                Ok(CallToolResult {
+                    content: vec![Content::from_raw(payload.to_string())],
+                    is_error: Some(false),
+                })
+            },
+            "unsubscribe" => {
+                let id = args.get("subscription_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| RmcpError::invalid_params("Missing 'subscription_id' parameter", None))?;
+
+                let removed = self.subscriptions.lock().await.remove(id).is_some();
+                let payload = json!({ "subscription_id": id, "removed": removed });
+                Ok(CallToolResult {
+                    content: vec![Content::from_raw(payload.to_string())],
+                    is_error: Some(false),
+                })
+            },
+            "add_synonym" => {
+                let term = args.get("term")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| RmcpError::invalid_params("Missing 'term' parameter", None))?;
+                let alias = args.get("alias")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| RmcpError::invalid_params("Missing 'alias' parameter", None))?;
+
+                self.db_service.add_synonym(term, alias).await.map_err(to_rmcp_error)?;
+                self.invalidate_synonyms().await;
+
+                let payload = json!({ "added": { "term": term, "alias": alias } });
+                Ok(CallToolResult {
+                    content: vec![Content::from_raw(payload.to_string())],
+                    is_error: Some(false),
+                })
+            },
+            "remove_synonym" => {
+                let term = args.get("term")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| RmcpError::invalid_params("Missing 'term' parameter", None))?;
+                let alias = args.get("alias")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| RmcpError::invalid_params("Missing 'alias' parameter", None))?;
+
+                let removed = self.db_service.remove_synonym(term, alias).await.map_err(to_rmcp_error)?;
+                self.invalidate_synonyms().await;
+
+                let payload = json!({ "removed": removed, "term": term, "alias": alias });
+                Ok(CallToolResult {
+                    content: vec![Content::from_raw(payload.to_string())],
+                    is_error: Some(false),
+                })
+            },
+            "update_chapter_summary" => {
+                let chapter_id = args.get("chapter_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| RmcpError::invalid_params("Missing 'chapter_id' parameter", None))?;
+                let summary = args.get("summary")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| RmcpError::invalid_params("Missing 'summary' parameter", None))?;
+
+                self.db_service.update_chapter_summary(chapter_id, summary).await.map_err(to_rmcp_error)?;
+
+                let payload = json!({ "updated": { "chapter_id": chapter_id } });
+                Ok(CallToolResult {
+                    content: vec![Content::from_raw(payload.to_string())],
+                    is_error: Some(false),
+                })
+            },
+            "get_context" => {
+                let query = args.get("query")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| RmcpError::invalid_params("Missing 'query' parameter", None))?;
+                let max_tokens = args.get("max_tokens")
+                    .and_then(|v| v.as_u64())
+                    .ok_or_else(|| RmcpError::invalid_params("Missing 'max_tokens' parameter", None))?
+                    as u32;
+
+                let search_params = QueryParser::parse_natural_language_query(query);
+                let context = self.db_service.assemble_context(&search_params, max_tokens).await
+                    .map_err(to_rmcp_error)?;
+
+                Ok(self.emit_content(&args, context.to_string()))
+            },
+            "list_synonyms" => {
+                let pairs = self.db_service.list_synonyms().await.map_err(to_rmcp_error)?;
+                let payload = json!({
+                    "synonyms": pairs.iter()
+                        .map(|(a, b)| json!({ "term": a, "alias": b }))
+                        .collect::<Vec<_>>(),
+                });
+                Ok(CallToolResult {
+                    content: vec![Content::from_raw(payload.to_string())],
+                    is_error: Some(false),
+                })
+            },
+            "get_database_stats" => {
+                let stats = self.db_service.database_stats().await.map_err(to_rmcp_error)?;
+                Ok(CallToolResult {
+                    content: vec![Content::from_raw(stats.to_string())],
+                    is_error: Some(false),
+                })
+            },
+            "get_similar_chapters" => {
+                let chapter_id = args.get("chapter_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| RmcpError::invalid_params("Missing 'chapter_id' parameter", None))?;
+                let limit = args.get("limit").and_then(|v| v.as_u64()).unwrap_or(5) as u32;
+
+                let response = self.db_service.get_similar_chapters(chapter_id, limit).await
+                    .map_err(to_rmcp_error)?;
+                // Emit the raw neighbor documents so each result keeps its
+                // `similarity_score` for the caller to threshold.
+                Ok(self.emit_content(&args, response.data.to_string()))
+            },
+            "get_similar_characters" => {
+                let character_id = args.get("character_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| RmcpError::invalid_params("Missing 'character_id' parameter", None))?;
+                let limit = args.get("limit").and_then(|v| v.as_u64()).unwrap_or(5) as u32;
+
+                let response = self.db_service.get_similar_characters(character_id, limit).await
+                    .map_err(to_rmcp_error)?;
+                Ok(self.emit_content(&args, response.data.to_string()))
+            },
+            "batch_query" => {
+                // Cap the fan-out so one request can't spawn an unbounded
+                // number of concurrent database queries.
+                const MAX_OPS: usize = 16;
+
+                let ops = args.get("operations")
+                    .and_then(|v| v.as_array())
+                    .ok_or_else(|| RmcpError::invalid_params("Missing 'operations' array", None))?;
+                if ops.len() > MAX_OPS {
+                    return Err(RmcpError::invalid_params(
+                        format!("Batch has {} operations, exceeding the limit of {}", ops.len(), MAX_OPS),
+                        None,
+                    ));
+                }
+
+                // Build one future per op, each dispatched through the shared
+                // per-tool logic, then run them concurrently.
+                let futures = ops.iter().enumerate().map(|(index, op)| async move {
+                    let tool = op.get("tool").and_then(|v| v.as_str())
+                        .ok_or_else(|| format!("operation {} is missing a 'tool' name", index))?;
+                    // Reject a nested batch so the MAX_OPS bound can't be escaped.
+                    if tool == "batch_query" {
+                        return Err(format!("operation {}: batch_query cannot be nested", index));
+                    }
+                    let op_args = match op.get("args").cloned() {
+                        Some(Value::Object(map)) => map,
+                        None => serde_json::Map::new(),
+                        Some(other) => return Err(format!(
+                            "operation {} 'args' must be an object, got {}", index, other)),
+                    };
+                    Box::pin(self.dispatch_tool(tool, op_args)).await
+                        .map(|result| {
+                            result.content.iter()
+                                .filter_map(|c| c.raw.as_text().map(|t| t.text.clone()))
+                                .collect::<Vec<_>>()
+                                .join("\n")
+                        })
+                        .map_err(|e| format!("tool '{}' failed: {}", tool, e))
+                });
+
+                // A per-op failure is captured in its slot rather than failing
+                // the whole batch, preserving input order.
+                let results: Vec<Value> = futures_util::future::join_all(futures).await
+                    .into_iter()
+                    .map(|outcome| match outcome {
+                        Ok(text) => json!({ "ok": true, "result": text }),
+                        Err(message) => json!({ "ok": false, "error": message }),
+                    })
+                    .collect();
+
+                Ok(CallToolResult {
+                    content: vec![Content::from_raw(Value::Array(results).to_string())],
+                    is_error: Some(false),
+                })
+            },
+            "run_plan" => {
+                // Guard against runaway plans: a small cap keeps a single
+                // request bounded no matter what the caller submits.
+                const MAX_STEPS: usize = 16;
+
+                let steps = args.get("steps")
+                    .and_then(|v| v.as_array())
+                    .ok_or_else(|| RmcpError::invalid_params("Missing 'steps' array", None))?;
+                if steps.len() > MAX_STEPS {
+                    return Err(RmcpError::invalid_params(
+                        format!("Plan has {} steps, exceeding the limit of {}", steps.len(), MAX_STEPS),
+                        None,
+                    ));
+                }
+
+                // Results bound by name so later steps can reference them.
+                let mut context: HashMap<String, Value> = HashMap::new();
+                let mut output = String::new();
+
+                for (index, step) in steps.iter().enumerate() {
+                    let tool = step.get("tool").and_then(|v| v.as_str())
+                        .ok_or_else(|| plan_error(index, "step is missing a 'tool' name"))?;
+                    let raw_args = step.get("args")
+                        .cloned()
+                        .unwrap_or_else(|| Value::Object(serde_json::Map::new()));
+
+                    // Substitute ${bind.path} references against earlier outputs
+                    // before the step runs, failing fast on an unbound reference.
+                    let resolved = resolve_placeholders(&raw_args, &context)
+                        .map_err(|e| plan_error(index, &e))?;
+                    let step_args = match resolved {
+                        Value::Object(map) => map,
+                        other => return Err(plan_error(index,
+                            &format!("step 'args' must be an object, got {}", other))),
+                    };
+
+                    // Recurse through the same dispatcher; boxed so the async
+                    // self-call has a finite-size future.
+                    let result = Box::pin(self.dispatch_tool(tool, step_args)).await
+                        .map_err(|e| plan_error(index, &format!("tool '{}' failed: {}", tool, e)))?;
+
+                    let text = result.content.iter()
+                        .filter_map(|c| c.raw.as_text().map(|t| t.text.clone()))
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    output.push_str(&format!("=== step {} ({}) ===\n{}\n\n", index + 1, tool, text));
+
+                    // Parse the payload back to a value so `${bind.field}`
+                    // references resolve structurally; fall back to the raw text.
+                    if let Some(bind) = step.get("bind").and_then(|v| v.as_str()) {
+                        let value = serde_json::from_str(&text).unwrap_or(Value::String(text));
+                        context.insert(bind.to_string(), value);
+                    }
+                }
+
+                Ok(CallToolResult {
+                    content: vec![Content::from_raw(output)],
                     is_error: Some(false),
                 })
             },
@@ -715,101 +1730,388 @@ impl<T: DatabaseService + Clone + Send + Sync + 'static> ServerHandler for MpcHa
     }
 }
 
-pub async fn sse_handler() -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+/// Build a structured error naming the plan step that failed.
+fn plan_error(index: usize, message: &str) -> RmcpError {
+    RmcpError::invalid_params(format!("run_plan step {}: {}", index + 1, message), None)
+}
+
+/// Recursively substitute `${bind.path}` placeholders inside a step's `args`
+/// against the outputs of earlier steps. A string that is exactly one
+/// placeholder is replaced by the referenced value verbatim (preserving its
+/// type); a placeholder embedded in surrounding text is stringified. An
+/// unbound or unresolvable reference is an error so a malformed plan fails
+/// fast rather than calling a tool with a literal `${...}` string.
+fn resolve_placeholders(value: &Value, context: &HashMap<String, Value>) -> Result<Value, String> {
+    match value {
+        Value::String(s) => resolve_string(s, context),
+        Value::Array(items) => items.iter()
+            .map(|v| resolve_placeholders(v, context))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array),
+        Value::Object(map) => {
+            let mut out = serde_json::Map::new();
+            for (k, v) in map {
+                out.insert(k.clone(), resolve_placeholders(v, context)?);
+            }
+            Ok(Value::Object(out))
+        }
+        other => Ok(other.clone()),
+    }
+}
+
+/// Resolve placeholders inside a single string.
+fn resolve_string(s: &str, context: &HashMap<String, Value>) -> Result<Value, String> {
+    // A whole-string placeholder keeps the referenced value's native type.
+    if let Some(path) = s.strip_prefix("${").and_then(|rest| rest.strip_suffix('}')) {
+        if !path.contains("${") {
+            return lookup_path(path, context);
+        }
+    }
+
+    // Otherwise splice each placeholder's stringified value into the text.
+    let mut out = String::new();
+    let mut rest = s;
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after.find('}')
+            .ok_or_else(|| format!("unterminated placeholder in '{}'", s))?;
+        let resolved = lookup_path(&after[..end], context)?;
+        match resolved {
+            Value::String(v) => out.push_str(&v),
+            other => out.push_str(&other.to_string()),
+        }
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(Value::String(out))
+}
+
+/// Walk a `bind.field[0].nested` path into a bound step result.
+fn lookup_path(path: &str, context: &HashMap<String, Value>) -> Result<Value, String> {
+    let mut chars = path.chars().peekable();
+    let mut segment = String::new();
+    let mut current: Option<&Value> = None;
+
+    // Split on '.' and '[' while keeping index tokens.
+    let mut tokens: Vec<String> = Vec::new();
+    while let Some(&c) = chars.peek() {
+        match c {
+            '.' => { if !segment.is_empty() { tokens.push(std::mem::take(&mut segment)); } chars.next(); }
+            '[' => {
+                if !segment.is_empty() { tokens.push(std::mem::take(&mut segment)); }
+                chars.next();
+                let mut idx = String::new();
+                while let Some(&d) = chars.peek() {
+                    if d == ']' { chars.next(); break; }
+                    idx.push(d);
+                    chars.next();
+                }
+                tokens.push(format!("#{}", idx));
+            }
+            _ => { segment.push(c); chars.next(); }
+        }
+    }
+    if !segment.is_empty() { tokens.push(segment); }
+
+    for token in &tokens {
+        current = match current {
+            None => context.get(token.as_str()),
+            Some(value) => {
+                if let Some(idx) = token.strip_prefix('#') {
+                    let i: usize = idx.parse().map_err(|_| format!("bad index '{}' in path '{}'", idx, path))?;
+                    value.get(i)
+                } else {
+                    value.get(token.as_str())
+                }
+            }
+        };
+        if current.is_none() {
+            return Err(format!("unbound reference '{}'", path));
+        }
+    }
+
+    current.cloned().ok_or_else(|| format!("unbound reference '{}'", path))
+}
+
+/// A broad search used to seed a subscription's backfill: no keyword or filter
+/// constraints, so every collection's most recent documents come back and the
+/// [`ReqFilter`] does the narrowing.
+fn broad_search_params() -> SearchParams {
+    SearchParams {
+        collection: "all".to_string(),
+        query_type: "search".to_string(),
+        keywords: Vec::new(),
+        filters: None,
+        limit: None,
+        page_token: None,
+        filter_expr: None,
+        compiled_filter: None,
+        text_query: None,
+    }
+}
+
+/// Retain only the documents in a combined `search_all` result that satisfy
+/// `filter`, capped per collection by the filter's `limit`.
+fn collect_matches(filter: &ReqFilter, combined: &Value) -> Value {
+    let mut out = serde_json::Map::new();
+    for collection in ["novels", "chapters", "characters", "qa"] {
+        let matched: Vec<Value> = combined[collection]
+            .as_array()
+            .map(|docs| {
+                docs.iter()
+                    .filter(|doc| filter.matches(collection, doc))
+                    .take(filter.limit.unwrap_or(usize::MAX))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        out.insert(collection.to_string(), Value::Array(matched));
+    }
+    Value::Object(out)
+}
+
+/// The `_id` of a combined-result document, parsed from its `{ "$oid": .. }`
+/// serialization. Used to decide whether a document is newer than the tail
+/// high-water mark.
+fn doc_object_id(doc: &Value) -> Option<ObjectId> {
+    let oid = doc.get("_id").and_then(|id| id.get("$oid")).and_then(|v| v.as_str())?;
+    ObjectId::parse_str(oid).ok()
+}
+
+/// Retain only the documents in a combined `search_all` result whose `_id` is
+/// newer than `watermark`, returning the pruned result and the newest id seen.
+/// Documents without a parseable id are dropped, since they cannot be ordered
+/// against the mark.
+fn retain_newer(combined: &Value, watermark: Option<ObjectId>) -> (Value, Option<ObjectId>) {
+    let mut newest = watermark;
+    let mut out = serde_json::Map::new();
+    for collection in ["novels", "chapters", "characters", "qa"] {
+        let fresh: Vec<Value> = combined[collection]
+            .as_array()
+            .map(|docs| {
+                docs.iter()
+                    .filter(|doc| match doc_object_id(doc) {
+                        Some(id) => {
+                            if newest.is_none_or(|mark| id > mark) {
+                                newest = Some(id);
+                            }
+                            watermark.is_none_or(|mark| id > mark)
+                        }
+                        None => false,
+                    })
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        out.insert(collection.to_string(), Value::Array(fresh));
+    }
+    (Value::Object(out), newest)
+}
+
+/// SSE endpoint that tails newly inserted documents and pushes an event to each
+/// matching subscription. A per-connection high-water mark on `_id` means each
+/// tick only evaluates documents inserted since the previous tick, so a
+/// subscription receives each new document once rather than the full matching
+/// set every second. The mark is seeded from the first tick's newest id so the
+/// backlog already delivered by `subscribe_content`'s backfill is not replayed.
+pub fn content_sse_handler<T: DatabaseService + Clone + Send + Sync + 'static>(
+    db_service: T,
+    subscriptions: Registry,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let watermark = Arc::new(std::sync::Mutex::new(None::<ObjectId>));
     let stream = IntervalStream::new(tokio::time::interval(Duration::from_secs(1)))
-        .enumerate()
-        .map(|(i, _)| Ok(Event::default().data(format!("tick: {}", i))));
+        .then(move |_| {
+            let db_service = db_service.clone();
+            let subscriptions = subscriptions.clone();
+            let watermark = watermark.clone();
+            async move {
+                let combined = match db_service.search_all(&broad_search_params()).await {
+                    Ok(value) => value,
+                    Err(_) => return Vec::new(),
+                };
+
+                // Prune to documents newer than the mark, then advance it. On
+                // the first tick the mark is unset, so nothing is emitted and
+                // the stream starts from the current tail.
+                let fresh = {
+                    let mut mark = watermark.lock().unwrap();
+                    let seeding = mark.is_none();
+                    let (fresh, newest) = retain_newer(&combined, *mark);
+                    *mark = newest;
+                    if seeding { return Vec::new(); }
+                    fresh
+                };
+
+                let registry = subscriptions.lock().await;
+                registry
+                    .iter()
+                    .filter_map(|(id, filter)| {
+                        let matched = collect_matches(filter, &fresh);
+                        let has_match = ["novels", "chapters", "characters", "qa"]
+                            .iter()
+                            .any(|c| matched[*c].as_array().is_some_and(|a| !a.is_empty()));
+                        if !has_match {
+                            return None;
+                        }
+                        let content = format_all_results(&matched);
+                        Some(Ok(Event::default().id(id.clone()).data(content.to_string())))
+                    })
+                    .collect::<Vec<_>>()
+            }
+        })
+        .flat_map(futures_util::stream::iter);
     Sse::new(stream)
 }
 
-// // Add this function to create a filter that adds server state to the request
-// fn with_server_state<T: DatabaseService + Clone + Send + Sync + 'static>(
-//     state: ServerState<T>
-// ) -> impl Filter<Extract = (ServerState<T>,), Error = std::convert::Infallible> + Clone {
-//     warp::any().map(move || state.clone())
-// }
-
-// async fn handle_mcp_request<T: DatabaseService + Clone + Send + Sync + 'static>(
-//     request: serde_json::Value, 
-//     state: ServerState<T>
-// ) -> Result<impl warp::Reply, warp::Rejection> {
-//     let mpc_handler = MpcHandler {
-//         db_service: state.db_service
-//     };
-    
-//     // Process the request manually since HttpService isn't available
-//     let response = match mpc_handler.handle_request(request).await {
-//         Ok(resp) => resp,
-//         Err(err) => json!({
-//             "error": {
-//                 "code": -32603,
-//                 "message": format!("Internal error: {}", err)
-//             }
-//         }),
-//     };
-    
-//     Ok(warp::reply::json(&response))
-// }
-
-// // Fix StdIO handler to manually implement the service since StdioService isn't available
-// pub async fn run_stdio_mcp_server<T: DatabaseService + Clone + Send + Sync + 'static>(
-//     state: ServerState<T>
-// ) -> Result<(), Box<dyn std::error::Error>> {
-//     let mpc_handler = MpcHandler {
-//         db_service: state.db_service.clone(),
-//     };
-    
-//     // Manual implementation of StdIO service
-//     let stdin = std::io::stdin();
-//     let mut stdin_lock = stdin.lock();
-//     let stdout = std::io::stdout();
-//     let mut stdout_lock = stdout.lock();
-    
-//     let mut buffer = String::new();
-    
-//     loop {
-//         buffer.clear();
-//         match stdin_lock.read_line(&mut buffer) {
-//             Ok(0) => break, // EOF
-//             Ok(_) => {
-//                 let request: serde_json::Value = match serde_json::from_str(&buffer) {
-//                     Ok(req) => req,
-//                     Err(e) => {
-//                         let error_response = json!({
-//                             "error": {
-//                                 "code": -32700,
-//                                 "message": format!("Parse error: {}", e)
-//                             }
-//                         });
-//                         serde_json::to_writer(&mut stdout_lock, &error_response)?;
-//                         writeln!(&mut stdout_lock)?;
-//                         continue;
-//                     }
-//                 };
-                
-//                 // Process the request
-//                 let response = match mpc_handler.handle_request(request).await {
-//                     Ok(resp) => resp,
-//                     Err(e) => {
-//                         json!({
-//                             "error": {
-//                                 "code": -32603,
-//                                 "message": format!("Internal error: {}", e)
-//                             }
-//                         })
-//                     }
-//                 };
-                
-//                 // Write the response
-//                 serde_json::to_writer(&mut stdout_lock, &response)?;
-//                 writeln!(&mut stdout_lock)?;
-//             }
-//             Err(e) => {
-//                 eprintln!("Error reading from stdin: {}", e);
-//                 break;
-//             }
-//         }
-//     }
-    
-//     Ok(())
-// }
\ No newline at end of file
+/// Health/metrics heartbeat: every few seconds it re-reads
+/// [`DatabaseService::database_stats`] and emits one `stats` event carrying the
+/// current dataset size and freshness, giving clients a live view without
+/// polling. A transient stats error is surfaced as an `error` event rather than
+/// tearing down the stream.
+pub fn sse_handler<T: DatabaseService + Clone + Send + Sync + 'static>(
+    db_service: T,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let stream = IntervalStream::new(tokio::time::interval(Duration::from_secs(5)))
+        .then(move |_| {
+            let db_service = db_service.clone();
+            async move {
+                match db_service.database_stats().await {
+                    Ok(stats) => Ok(Event::default().event("stats").data(stats.to_string())),
+                    Err(e) => Ok(Event::default()
+                        .event("error")
+                        .data(json!({ "error": e.to_string() }).to_string())),
+                }
+            }
+        });
+    Sse::new(stream)
+}
+
+impl<T: DatabaseService + Clone + Send + Sync + 'static> MpcHandler<T> {
+    /// Dispatch a single JSON-RPC request object and build its response object,
+    /// sharing the same tool dispatch path used by the SSE/HTTP transport. The
+    /// `tools/call` method takes `{ name, arguments }`; for convenience a bare
+    /// tool name as the method with `params` as the arguments is also accepted.
+    pub async fn handle_jsonrpc(&self, request: Value) -> Value {
+        let id = request.get("id").cloned().unwrap_or(Value::Null);
+        let method = request.get("method").and_then(|v| v.as_str()).unwrap_or_default();
+
+        let (tool, args_value) = if method == "tools/call" {
+            let params = request.get("params").cloned().unwrap_or(Value::Null);
+            let name = params.get("name").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+            let arguments = params.get("arguments").cloned().unwrap_or(Value::Null);
+            (name, arguments)
+        } else {
+            (method.to_string(), request.get("params").cloned().unwrap_or(Value::Null))
+        };
+
+        let args = match args_value {
+            Value::Object(map) => map,
+            Value::Null => serde_json::Map::new(),
+            _ => return jsonrpc_error(id, -32602, "params must be an object"),
+        };
+
+        match self.dispatch_tool(&tool, args).await {
+            Ok(result) => {
+                let text = result.content.iter()
+                    .filter_map(|c| c.raw.as_text().map(|t| t.text.clone()))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                json!({ "jsonrpc": "2.0", "id": id, "result": { "content": text } })
+            }
+            // All tool failures share one mapping point so error semantics are
+            // identical across the stdio, SSE, and HTTP transports.
+            Err(err) => jsonrpc_error(id, err.code.0, &err.message),
+        }
+    }
+}
+
+/// Build a JSON-RPC 2.0 error response object.
+fn jsonrpc_error(id: Value, code: i32, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+/// Run the MCP server over stdio using newline-delimited JSON-RPC, the framing
+/// most editor/desktop clients launch servers with. Each line is parsed,
+/// dispatched through the shared [`MpcHandler::handle_jsonrpc`] path, and its
+/// response written to stdout; a malformed line yields a `-32700` parse error
+/// and reading past EOF shuts the loop down cleanly.
+pub async fn run_stdio_mcp_server<T: DatabaseService + Clone + Send + Sync + 'static>(
+    handler: MpcHandler<T>,
+) -> Result<(), Box<dyn Error>> {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut stdout = tokio::io::stdout();
+
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<Value>(&line) {
+            Ok(request) => handler.handle_jsonrpc(request).await,
+            Err(e) => jsonrpc_error(Value::Null, -32700, &format!("Parse error: {}", e)),
+        };
+
+        let mut bytes = serde_json::to_vec(&response)?;
+        bytes.push(b'\n');
+        stdout.write_all(&bytes).await?;
+        stdout.flush().await?;
+    }
+
+    Ok(())
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(pairs: &[(&str, &str)]) -> serde_json::Map<String, Value> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), json!(v)))
+            .collect()
+    }
+
+    #[test]
+    fn query_database_maps_to_the_requested_collection() {
+        let a = args(&[("collection", "chapters")]);
+        assert_eq!(resource_for("query_database", &a), Some(("chapters".to_string(), "read")));
+    }
+
+    #[test]
+    fn query_database_without_a_collection_fans_out_to_all() {
+        let a = args(&[]);
+        assert_eq!(resource_for("query_database", &a), Some(("all".to_string(), "read")));
+    }
+
+    #[test]
+    fn synonym_mutations_require_write_on_the_synonyms_pseudo_collection() {
+        let a = args(&[]);
+        assert_eq!(resource_for("add_synonym", &a), Some(("synonyms".to_string(), "write")));
+        assert_eq!(resource_for("remove_synonym", &a), Some(("synonyms".to_string(), "write")));
+    }
+
+    #[test]
+    fn update_chapter_summary_requires_write_on_chapters() {
+        let a = args(&[]);
+        assert_eq!(resource_for("update_chapter_summary", &a), Some(("chapters".to_string(), "write")));
+    }
+
+    #[test]
+    fn meta_tools_map_to_no_single_resource() {
+        let a = args(&[]);
+        assert_eq!(resource_for("batch_query", &a), None);
+        assert_eq!(resource_for("run_plan", &a), None);
+    }
+
+    #[test]
+    fn get_context_requires_read_on_all_collections() {
+        let a = args(&[]);
+        assert_eq!(resource_for("get_context", &a), Some(("all".to_string(), "read")));
+    }
+}
@@ -1,7 +1,14 @@
-mod crud_handler;
+pub mod admin_handler;
+pub mod cover_handler;
+pub mod crud_handler;
+pub mod error;
+pub mod openapi;
 // Make module public so it can be accessed from main.rs
 pub mod mcp_handler;
+pub mod router_macros;
 mod rmcp_handler;
 
+pub use admin_handler::{admin_router, analytics_router};
 pub use crud_handler::*;
-pub use rmcp_handler::{rmcp_http_handler, run_stdio_mcp_server, ServerState};
\ No newline at end of file
+pub use rmcp_handler::{rmcp_http_handler, ServerState};
+pub use mcp_handler::run_stdio_mcp_server;
\ No newline at end of file
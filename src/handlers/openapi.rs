@@ -0,0 +1,73 @@
+//! Generated OpenAPI contract and Swagger UI.
+//!
+//! [`ApiDoc`] aggregates every annotated path and component schema into a single
+//! spec so clients — including LLM tool-callers driving the MCP server — get a
+//! machine-readable description of request bodies, path params, and the
+//! structured error responses instead of reverse-engineering [`api_router`].
+
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
+
+use crate::handlers::crud_handler;
+use crate::handlers::error::ApiErrorBody;
+use crate::models::{Chapter, Character, Novel, NovelMetadata, Relationship, QA};
+
+/// The aggregated OpenAPI document for the CRUD + search surface.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "MCP Novel Server API",
+        description = "CRUD and fuzzy-search endpoints backing the novel MCP server.",
+        version = env!("CARGO_PKG_VERSION"),
+    ),
+    paths(
+        crud_handler::get_novels,
+        crud_handler::get_novel,
+        crud_handler::create_novel,
+        crud_handler::update_novel,
+        crud_handler::delete_novel,
+        crud_handler::get_chapters,
+        crud_handler::get_novel_chapters,
+        crud_handler::create_chapter,
+        crud_handler::get_chapter,
+        crud_handler::update_chapter,
+        crud_handler::delete_chapter,
+        crud_handler::get_characters,
+        crud_handler::get_novel_characters,
+        crud_handler::create_character,
+        crud_handler::get_character,
+        crud_handler::update_character,
+        crud_handler::delete_character,
+        crud_handler::get_qa_entries,
+        crud_handler::create_qa,
+        crud_handler::get_qa,
+        crud_handler::update_qa,
+        crud_handler::delete_qa,
+        crud_handler::search,
+        crud_handler::search_kind,
+        crud_handler::login,
+    ),
+    components(schemas(
+        Novel,
+        NovelMetadata,
+        Chapter,
+        Character,
+        Relationship,
+        QA,
+        ApiErrorBody,
+    )),
+    tags(
+        (name = "novels", description = "Novel metadata"),
+        (name = "chapters", description = "Chapter text and summaries"),
+        (name = "characters", description = "Character profiles"),
+        (name = "qa", description = "Knowledge-base Q&A entries"),
+        (name = "search", description = "Fuzzy full-text retrieval"),
+        (name = "auth", description = "Token issuance"),
+    ),
+)]
+pub struct ApiDoc;
+
+/// Swagger UI at `/api/docs` serving the spec from `/api/openapi.json`.
+pub fn swagger_ui() -> SwaggerUi {
+    SwaggerUi::new("/api/docs").url("/api/openapi.json", ApiDoc::openapi())
+}
@@ -9,8 +9,4 @@ pub struct ServerState<T: DatabaseService> {
 
 pub async fn rmcp_http_handler() {
     // This is a placeholder - use mcp_http_handler instead
-}
-
-pub async fn run_stdio_mcp_server() {
-    // This is a placeholder - use the implementation in mcp_handler.rs
 }
\ No newline at end of file
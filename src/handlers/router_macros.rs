@@ -0,0 +1,52 @@
+//! Declarative helpers for wiring axum routes and translating handler errors
+//! onto a single, consistent HTTP/MCP response path.
+//!
+//! The admin and CRUD surfaces grow one `(method, path, handler)` triple at a
+//! time; [`route_table`] turns that list into a [`axum::Router`] so new
+//! endpoints are one line instead of a `Router::new().route(...)` chain. The
+//! MCP direct-handlers share [`tool_result`], which collapses the repeated
+//! "run the tool, wrap `Ok` into a [`CallToolResult`], map `Err` onto
+//! [`rmcp::Error::invalid_params`]" block that every `handle_*` method used to
+//! spell out by hand.
+
+/// Build a [`axum::Router`] from a declarative list of `method path => handler`
+/// entries, all sharing a single piece of state applied with `.with_state`.
+///
+/// ```ignore
+/// let router = route_table!(state, {
+///     get "/health" => health,
+///     get "/metrics" => metrics,
+/// });
+/// ```
+macro_rules! route_table {
+    ($state:expr, { $($method:ident $path:literal => $handler:path),* $(,)? }) => {{
+        axum::Router::new()
+            $(.route($path, axum::routing::$method($handler)))*
+            .with_state($state)
+    }};
+}
+
+/// Run an MCP tool call named `$tool` and translate its `Result<String, String>`
+/// into the `Result<CallToolResult, rmcp::Error>` the HTTP handlers return,
+/// recording a tool error and mapping a failure onto `invalid_params` exactly
+/// once.
+macro_rules! tool_result {
+    ($tool:literal, $call:expr) => {
+        match $call {
+            Ok(content) => Ok(rmcp::model::CallToolResult {
+                content: vec![rmcp::model::Annotated::new(
+                    rmcp::model::RawContent::text(content),
+                    None,
+                )],
+                is_error: None,
+            }),
+            Err(e) => {
+                $crate::utils::metrics::metrics().record_tool_error($tool);
+                Err(rmcp::Error::invalid_params(e, None))
+            }
+        }
+    };
+}
+
+pub(crate) use route_table;
+pub(crate) use tool_result;
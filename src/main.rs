@@ -3,13 +3,17 @@ use std::sync::Arc;
 
 use rmcp::transport::sse_server::{SseServer, SseServerConfig};
 use axum::{
-    routing::post,
+    middleware::from_fn_with_state,
+    routing::get,
     Router,
 };
 use dotenv::dotenv;
 use tokio::net::TcpListener;
 use tokio_util::sync::CancellationToken;
 
+mod auth;
+mod cli;
+mod config;
 mod db;
 mod handlers;
 mod mcp;
@@ -17,8 +21,10 @@ mod models;
 mod services;
 mod utils;
 
+use crate::auth::{auth_middleware, AuthState};
+use crate::config::Config;
 use crate::handlers::{
-    api_router, 
+    api_router,
     mcp_handler::{MpcHandler, ServerState}
 };
 use crate::services::{
@@ -30,25 +36,96 @@ use crate::services::{
 async fn main() -> anyhow::Result<()> {
     // Load environment variables from .env file
     dotenv().ok();
-    
+
     // Initialize tracing for logs
     tracing_subscriber::fmt::init();
-    
-    // Get base port from environment or use default
-    let base_port = std::env::var("PORT")
-        .unwrap_or_else(|_| "3000".to_string())
-        .parse::<u16>()
-        .expect("PORT must be a number");
 
-    let sse_port = base_port;
-    let api_port = base_port + 1; // Assign a different port for CRUD API
+    let cli: cli::Cli = argh::from_env();
+
+    // Load layered config: defaults ⟵ TOML file (--config / CONFIG_PATH) ⟵ env.
+    let config = crate::config::Config::load(cli.config.as_deref())?;
+
+    // Dispatch management subcommands; `serve` (the default) runs the servers.
+    match cli.command {
+        None => serve(config, false).await,
+        Some(cli::Command::Serve(cmd)) => serve(config, cmd.stdio).await,
+        Some(cli::Command::Query(cmd)) => cli::run_query(&config, cmd).await,
+        Some(cli::Command::Ls(cmd)) => cli::run_ls(&config, cmd).await,
+        Some(cli::Command::Token(cmd)) => cli::run_token(&config, cmd).await,
+        Some(cli::Command::Migrate(_)) => cli::run_migrate(&config).await,
+    }
+}
+
+/// Run the SSE + CRUD/admin servers until Ctrl+C, or — when `stdio` is set —
+/// the newline-delimited JSON-RPC MCP server over stdin/stdout.
+async fn serve(config: Config, stdio: bool) -> anyhow::Result<()> {
+    let sse_port = config.server.sse_port;
+    let api_port = config.server.api_port;
+    let max_page_size = config.server.max_page_size;
+    let max_upload_bytes = config.server.max_upload_bytes;
+    let mcp_page_size = config.server.mcp_page_size;
+    let jwt_secret = config.auth.jwt_secret.clone();
+    let protect_reads = config.auth.protect_reads;
 
     // Create database service (shared)
-    let db_service: Arc<MongoDBService> = Arc::new(MongoDBService::new().await?);
-    let db_connection = db_service.get_db_connection(); // Assuming a method to get the raw connection for CRUD
+    let db_service: Arc<MongoDBService> = Arc::new(MongoDBService::new(&config.mongodb).await?);
+    let db_connection = db_service.db_connection();
+
+    // Run any pending schema/index migrations before the servers bind so the
+    // regex tools can assume their indexes exist.
+    crate::db::migrator::run_pending(&db_connection).await?;
+
+    // Build the Casbin-backed authorization layer from the model/policy paths.
+    // Shared by both transports so every tool call is enforced before it
+    // touches the database.
+    let model_path = &config.auth.model_path;
+    let policy_path = &config.auth.policy_path;
+    let authz = crate::auth::Authorization::new(model_path, policy_path).await?;
+    tracing::info!("Loaded RBAC authorization from {} / {}", model_path, policy_path);
+
+    // Database-backed token store used by the MCP handler to resolve a
+    // presented token to its authorization subject before enforcement.
+    let mcp_token_store = crate::auth::TokenStore::new(db_connection.clone());
+
+    // Semantic-search backend, shared by both transports so a `similar`/`like`
+    // query is answered by meaning rather than literal matching. Atlas
+    // deployments (a `mongodb+srv` URI) push ranking into `$vectorSearch`;
+    // self-hosted MongoDB falls back to in-process cosine ranking.
+    let embedder: Arc<dyn crate::services::embedding::Embedder> =
+        Arc::new(crate::services::embedding::HashingEmbedder::default());
+    let atlas = config.mongodb.uri.starts_with("mongodb+srv");
+    let vector_search = Arc::new(crate::services::vector_search::VectorSearchService::new(
+        db_connection.clone(),
+        embedder,
+        atlas,
+    ));
+
+    // Formatted-result cache for the regex search tools, shared by both
+    // transports: Redis when configured, an in-memory LRU otherwise.
+    let result_cache = crate::services::cache::from_config(&config.cache);
+
+    // stdio transport: a single MCP handler speaking newline-delimited JSON-RPC
+    // on stdin/stdout, as launched by editor/desktop clients. It carries the
+    // same authorization layer as the networked transport.
+    if stdio {
+        let handler = MpcHandler::new(db_service.clone())
+            .with_page_size(mcp_page_size)
+            .with_vector_search(vector_search.clone())
+            .with_cache(result_cache.clone())
+            .with_authorization(authz, mcp_token_store);
+        crate::handlers::run_stdio_mcp_server(handler).await
+            .map_err(|e| anyhow::anyhow!("stdio MCP server failed: {}", e))?;
+        return Ok(());
+    }
+
+    // Subscription registry shared between the served MCP handler (which
+    // registers subscriptions via `subscribe_content`) and the content-tailing
+    // SSE endpoint mounted on the API listener below.
+    let mcp_subscriptions = crate::mcp::subscription::new_registry();
 
     // --- Start SSE Server ---
-    let sse_addr = SocketAddr::from(([0, 0, 0, 0], sse_port));
+    let bind_ip: std::net::IpAddr = config.server.bind_addr.parse()?;
+    let sse_addr = SocketAddr::new(bind_ip, sse_port);
     let sse_cancellation_token = CancellationToken::new();
     let config = SseServerConfig {
         bind: sse_addr,
@@ -58,8 +135,19 @@ async fn main() -> anyhow::Result<()> {
     };
     // Use the db_service clone for the SSE handler
     let sse_server = SseServer::serve_with_config(config).await?;
-    // Start the SSE server by attaching the service; it runs in the background
-    let ct = sse_server.with_service(move || MpcHandler::new(db_service.clone()));
+    // Start the SSE server by attaching the service; it runs in the background.
+    // Each connection gets a handler wired with the authorization layer and
+    // token store so every tool call is enforced before touching the database.
+    let sse_db_service = db_service.clone();
+    let sse_subscriptions = mcp_subscriptions.clone();
+    let ct = sse_server.with_service(move || {
+        MpcHandler::new(sse_db_service.clone())
+            .with_page_size(mcp_page_size)
+            .with_subscriptions(sse_subscriptions.clone())
+            .with_vector_search(vector_search.clone())
+            .with_cache(result_cache.clone())
+            .with_authorization(authz.clone(), mcp_token_store.clone())
+    });
     tracing::info!("SSE Server listening on http://{}", sse_addr);
     tracing::info!("MCP SSE endpoint available at http://{}:{}/sse", sse_addr.ip(), sse_port);
     tracing::info!("MCP POST endpoint available at http://{}:{}/message", sse_addr.ip(), sse_port);
@@ -71,6 +159,23 @@ async fn main() -> anyhow::Result<()> {
     let chapter_service = Arc::new(ChapterCrudService::new(db_connection.clone()));
     let character_service = Arc::new(CharacterCrudService::new(db_connection.clone()));
     let qa_service = Arc::new(QACrudService::new(db_connection.clone()));
+    let token_store = Arc::new(crate::auth::TokenStore::new(db_connection.clone()));
+    let search_service = Arc::new(
+        crate::services::fulltext_search::FullTextSearchService::new(db_connection.clone()),
+    );
+    let cover_service = Arc::new(
+        crate::services::cover_service::CoverService::new(db_connection.clone()),
+    );
+
+    // Same auth gate `api_router` applies to its own routers, reused here so
+    // the admin, analytics, and stream surfaces don't leak novel/chapter
+    // content or DB stats to unauthenticated callers once `protect_reads` is
+    // on.
+    let auth_state = AuthState {
+        secret: jwt_secret.clone(),
+        protect_reads,
+    };
+    let auth_layer = || from_fn_with_state(auth_state.clone(), auth_middleware);
 
     // Build CRUD API router
     let api_app = api_router(
@@ -78,11 +183,51 @@ async fn main() -> anyhow::Result<()> {
         chapter_service,
         character_service,
         qa_service,
+        token_store.clone(),
+        search_service,
+        cover_service,
+        max_page_size,
+        max_upload_bytes,
+        jwt_secret,
+        protect_reads,
     );
-    let app = Router::new().merge(api_app); // Only includes /api routes
+    // Operability surface (health/metrics/collection stats) mounted next to the
+    // CRUD API on the same listener.
+    let admin_app = crate::handlers::admin_router(Arc::new(db_connection.clone()))
+        .layer(auth_layer());
+
+    // Aggregation analytics over the novel collections, served next to the
+    // admin surface on the same listener.
+    let analytics_service = Arc::new(
+        crate::services::analytics::AnalyticsService::new(db_connection.clone()),
+    );
+    let analytics_app = crate::handlers::analytics_router(analytics_service)
+        .layer(auth_layer());
+
+    // Live SSE streams on the API listener: a stats heartbeat and a tail of
+    // newly inserted documents matching the handler's active subscriptions.
+    let stats_db = db_service.clone();
+    let content_db = db_service.clone();
+    let stream_app = Router::new()
+        .route("/mcp/stats", get(move || {
+            let db = stats_db.clone();
+            async move { crate::handlers::mcp_handler::sse_handler(db) }
+        }))
+        .route("/mcp/content", get(move || {
+            let db = content_db.clone();
+            let subs = mcp_subscriptions.clone();
+            async move { crate::handlers::mcp_handler::content_sse_handler(db, subs) }
+        }))
+        .layer(auth_layer());
+
+    let app = Router::new()
+        .merge(api_app)
+        .merge(admin_app)
+        .merge(analytics_app)
+        .merge(stream_app);
 
     // Run CRUD API server on its own port
-    let api_addr = SocketAddr::from(([0, 0, 0, 0], api_port));
+    let api_addr = SocketAddr::new(bind_ip, api_port);
     tracing::info!("CRUD API Server listening on http://{}", api_addr);
     tracing::info!("CRUD API endpoints available at http://{}:{}/api/...", api_addr.ip(), api_port);
 
@@ -105,16 +250,3 @@ async fn main() -> anyhow::Result<()> {
     tracing::info!("Servers shut down gracefully.");
     Ok(())
 }
-
-// Helper extension trait to get DB connection (example)
-trait DbServiceExt {
-    fn get_db_connection(&self) -> crate::db::DatabaseConnection;
-}
-
-impl DbServiceExt for MongoDBService {
-    fn get_db_connection(&self) -> crate::db::DatabaseConnection {
-        // Assuming MongoDBService has a field `db` of type DatabaseConnection
-        // Adjust this based on your actual MongoDBService implementation
-        self.db.clone()
-    }
-}
@@ -0,0 +1,96 @@
+//! Response token budgeting.
+//!
+//! [`RequestContext`](crate::mcp::conversion::RequestContext) carries a
+//! `max_tokens` budget, but nothing enforced it: a large chapter or a wide
+//! character list could blow an agent's context window. [`fit_to_budget`]
+//! counts the assembled content with a pluggable [`TokenEstimator`] (obtained
+//! through [`estimator_for`], which returns the char-ratio estimator for every
+//! `options.model`) and, when it overflows, progressively degrades the payload
+//! — first dropping `Chapter.content` in favour of `summary`/`key_points`, then
+//! trimming `Character.relationships`, then paginating — reporting what it did
+//! through a [`BudgetReport`] so the handler can fill `has_more`/`remaining`.
+
+use serde_json::Value;
+
+/// Estimates the token cost of serialized content. Pluggable so a deployment
+/// can swap the rough default for a real BPE counter without touching callers.
+pub trait TokenEstimator {
+    fn count(&self, value: &Value) -> u32;
+}
+
+/// The default estimator: roughly four characters of serialized JSON per token,
+/// matching [`DatabaseService`](crate::services::DatabaseService)'s own
+/// heuristic so counts stay consistent across the codebase.
+pub struct CharRatioEstimator;
+
+impl TokenEstimator for CharRatioEstimator {
+    fn count(&self, value: &Value) -> u32 {
+        let json = serde_json::to_string(value).unwrap_or_default();
+        (json.len() as u32 + 3) / 4
+    }
+}
+
+/// Pick an estimator for the model named in `options.model`. Every model, named
+/// or missing, gets the char-ratio estimator; the model name is not consulted
+/// and no BPE vocabulary is available.
+pub fn estimator_for(_model: Option<&str>) -> Box<dyn TokenEstimator + Send + Sync> {
+    Box::new(CharRatioEstimator)
+}
+
+/// What [`fit_to_budget`] did to a payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BudgetReport {
+    /// Estimated token cost of the returned (possibly degraded) content.
+    pub token_count: u32,
+    /// Budget left over, clamped at zero.
+    pub remaining: u32,
+    /// Whether documents were dropped to fit, so the caller should paginate.
+    pub has_more: bool,
+}
+
+/// Shrink `docs` (an array of `collection` documents) to fit `max_tokens`,
+/// degrading in increasing order of information loss. A `max_tokens` of zero
+/// disables budgeting and the payload is returned untouched. Returns the final
+/// token count, remaining budget, and whether truncation occurred.
+pub fn fit_to_budget(
+    docs: &mut Vec<Value>,
+    collection: &str,
+    max_tokens: u32,
+    estimator: &dyn TokenEstimator,
+) -> BudgetReport {
+    if max_tokens == 0 {
+        let token_count = estimator.count(&Value::Array(docs.clone()));
+        return BudgetReport { token_count, remaining: 0, has_more: false };
+    }
+
+    let count = |docs: &Vec<Value>| estimator.count(&Value::Array(docs.clone()));
+
+    // Step 1: drop full chapter content, keeping the summary and key points.
+    if count(docs) > max_tokens && collection == "chapters" {
+        for doc in docs.iter_mut() {
+            if let Some(obj) = doc.as_object_mut() {
+                obj.remove("content");
+            }
+        }
+    }
+
+    // Step 2: trim character relationships, the next-largest optional field.
+    if count(docs) > max_tokens && collection == "characters" {
+        for doc in docs.iter_mut() {
+            if let Some(obj) = doc.as_object_mut() {
+                obj.remove("relationships");
+            }
+        }
+    }
+
+    // Step 3: paginate — drop whole documents from the tail until the page fits.
+    let mut has_more = false;
+    while count(docs) > max_tokens && docs.len() > 1 {
+        docs.pop();
+        has_more = true;
+    }
+
+    let token_count = count(docs);
+    let remaining = max_tokens.saturating_sub(token_count);
+    BudgetReport { token_count, remaining, has_more }
+}
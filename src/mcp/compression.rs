@@ -0,0 +1,88 @@
+//! Optional payload compression for MCP tool results.
+//!
+//! [`MCPResponse`](crate::models::MCPResponse) is meant for tight LLM context
+//! budgets, yet full chapter content and multi-entity search results can be
+//! large. When a serialized body exceeds [`DEFAULT_THRESHOLD`] bytes and the
+//! client advertised support through `options.accept_encoding`, [`encode`]
+//! gzip-compresses it and returns a base64 blob tagged with a
+//! `content_encoding` marker, recording both pre- and post-compression sizes so
+//! the caller can populate
+//! [`ResponseMetadata`](crate::models::ResponseMetadata).
+
+use std::io::Write;
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+
+use crate::services::page_token::base64url_encode;
+
+/// Minimum serialized size, in bytes, before a body is worth compressing.
+pub const DEFAULT_THRESHOLD: usize = 2048;
+
+/// The outcome of a compression attempt: either the untouched body or a
+/// base64-encoded gzip blob, with the sizes needed for response metadata.
+#[derive(Debug, Clone)]
+pub struct Encoded {
+    /// The body to send: raw JSON when `encoding` is `None`, base64 gzip
+    /// otherwise.
+    pub body: String,
+    /// The applied encoding, e.g. `"gzip"`, or `None` when sent as plain JSON.
+    pub encoding: Option<String>,
+    pub uncompressed_bytes: usize,
+    pub compressed_bytes: usize,
+}
+
+/// Compress `content` when the client supports it and the body is large enough,
+/// otherwise return it unchanged. `accept` is the client's advertised encoding
+/// list from `options.accept_encoding`; only `gzip` is negotiated.
+pub fn encode(content: &str, accept: &[String], threshold: usize) -> Encoded {
+    let uncompressed_bytes = content.len();
+    let client_supports_gzip = accept.iter().any(|e| e.eq_ignore_ascii_case("gzip"));
+
+    if !client_supports_gzip || uncompressed_bytes < threshold {
+        return Encoded {
+            body: content.to_string(),
+            encoding: None,
+            uncompressed_bytes,
+            compressed_bytes: uncompressed_bytes,
+        };
+    }
+
+    match gzip(content.as_bytes()) {
+        Ok(compressed) => {
+            let compressed_bytes = compressed.len();
+            Encoded {
+                body: base64url_encode(&compressed),
+                encoding: Some("gzip".to_string()),
+                uncompressed_bytes,
+                compressed_bytes,
+            }
+        }
+        // A compression failure is never fatal: fall back to the plain body.
+        Err(_) => Encoded {
+            body: content.to_string(),
+            encoding: None,
+            uncompressed_bytes,
+            compressed_bytes: uncompressed_bytes,
+        },
+    }
+}
+
+/// Read the client's advertised encodings from the `accept_encoding` request
+/// option, accepting either a single string or an array of strings.
+pub fn accepted_encodings(options: &serde_json::Value) -> Vec<String> {
+    match options.get("accept_encoding") {
+        Some(serde_json::Value::String(s)) => vec![s.clone()],
+        Some(serde_json::Value::Array(items)) => items
+            .iter()
+            .filter_map(|v| v.as_str().map(str::to_string))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn gzip(bytes: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(bytes)?;
+    encoder.finish()
+}
@@ -33,6 +33,16 @@ pub struct RequestContext {
     pub remaining_tokens: i32,
 }
 
+impl RequestContext {
+    /// Record that `used` tokens were spent assembling the response, updating
+    /// `token_count` and the derived `remaining_tokens` (clamped at zero) so a
+    /// caller can chain further requests within a fixed context window.
+    pub fn settle(&mut self, used: u32) {
+        self.token_count = used as i32;
+        self.remaining_tokens = (self.max_tokens - used as i32).max(0);
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct MpcError {
     pub code: i32,
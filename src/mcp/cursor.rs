@@ -0,0 +1,60 @@
+//! Opaque offset cursors for MCP tool and listing pagination.
+//!
+//! Unlike the keyset [`PageToken`](crate::services::page_token::PageToken) used
+//! by the database search path, MCP tools page over an already-materialised
+//! result slice, so a simple `offset`/`page_size` window suffices. The cursor
+//! also carries the collection it was minted for and a hash of the originating
+//! query; a cursor presented against a different query is rejected rather than
+//! silently returning an unrelated page.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::services::page_token::{base64url_decode, base64url_encode};
+
+/// The decoded contents of an MCP pagination cursor.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cursor {
+    pub collection: String,
+    pub query_hash: u64,
+    pub offset: usize,
+    pub page_size: usize,
+}
+
+impl Cursor {
+    pub fn new(collection: &str, query_hash: u64, offset: usize, page_size: usize) -> Self {
+        Self {
+            collection: collection.to_string(),
+            query_hash,
+            offset,
+            page_size,
+        }
+    }
+
+    /// A stable hash of the query string a cursor is bound to.
+    pub fn hash_query(query: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        query.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Encode the cursor as an opaque base64url-encoded JSON blob.
+    pub fn encode(&self) -> String {
+        let bytes = serde_json::to_vec(self).unwrap_or_default();
+        base64url_encode(&bytes)
+    }
+
+    /// Decode a cursor and validate that it was minted for the same query.
+    /// Returns an error on a malformed cursor or a `query_hash` mismatch.
+    pub fn decode(token: &str, query_hash: u64) -> Result<Cursor> {
+        let bytes = base64url_decode(token).context("invalid cursor")?;
+        let cursor: Cursor = serde_json::from_slice(&bytes).context("malformed cursor")?;
+        if cursor.query_hash != query_hash {
+            bail!("cursor was issued for a different query");
+        }
+        Ok(cursor)
+    }
+}
@@ -1,6 +1,11 @@
+pub mod budget;
+pub mod compression;
 pub mod conversion;
+pub mod cursor;
 pub mod protocol;
 pub mod server;
+pub mod subscription;
+pub mod tool_schema;
 
 use rmcp::{
     model::{
@@ -1,20 +1,60 @@
 // src/mcp/server.rs
-use rmcp::{model::{ServerInfo, CallToolResult, RawContent, Annotated}, ServerHandler, tool, Error};
+use rmcp::{model::{ServerInfo, CallToolResult}, ServerHandler, tool, Error};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use crate::auth::{Authorization, TokenStore};
+use crate::handlers::router_macros::tool_result;
+use crate::services::cache::{cache_key, ResultCache};
 use crate::services::DatabaseService;
+use crate::utils::metrics::metrics;
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct MCPDatabaseServer<T: DatabaseService + Clone + Send + Sync + 'static> {
     db_service: Arc<T>,
+    authz: Authorization,
+    token_store: TokenStore,
+    cache: Arc<dyn ResultCache>,
 }
 
 impl<T: DatabaseService + Clone + Send + Sync + 'static> MCPDatabaseServer<T> {
-    pub fn new(db_service: T) -> Self {
+    pub fn new(
+        db_service: T,
+        authz: Authorization,
+        token_store: TokenStore,
+        cache: Arc<dyn ResultCache>,
+    ) -> Self {
         Self {
             db_service: Arc::new(db_service),
+            authz,
+            token_store,
+            cache,
+        }
+    }
+
+    /// Resolve the caller identity carried by a token into an authorization
+    /// subject by looking the hashed token up in the [`TokenStore`]. An unknown
+    /// token resolves to itself so Casbin `g` lines can still grant it directly,
+    /// letting the enforcer make the final allow/deny decision.
+    async fn subject_for_token(&self, token: &str) -> String {
+        match self.token_store.resolve(token).await {
+            Ok(Some(resolved)) => resolved.subject,
+            _ => token.to_string(),
+        }
+    }
+
+    /// Enforce that `subject` may perform `action` on `collection`, returning a
+    /// denial as an `Err` message, which `tool_result!` maps onto
+    /// `invalid_params` for the direct handlers.
+    async fn enforce(&self, subject: &str, collection: &str, action: &str) -> Result<(), String> {
+        match self.authz.enforce(subject, collection, action).await {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(format!(
+                "Access denied: '{}' may not {} '{}'",
+                subject, action, collection
+            )),
+            Err(e) => Err(format!("Authorization error: {}", e)),
         }
     }
 }
@@ -203,13 +243,33 @@ pub struct UpdateChapterSummaryRequest {
 // MCP tool implementations - implementing each separately to avoid macro issues
 impl<T: DatabaseService + Clone + Send + Sync + 'static> MCPDatabaseServer<T> {
     #[tool(description = "Execute a natural language query against the database")]
-    pub async fn query(&self, #[tool(param)] query: String) -> Result<String, String> {
+    pub async fn query(
+        &self,
+        #[tool(param)] query: String,
+        #[tool(param)] auth_token: String,
+    ) -> Result<String, String> {
         use crate::utils::QueryParser;
-        
+
+        metrics().record_tool_call("query");
+
         // Parse the natural language query into structured params
         let search_params = QueryParser::parse_natural_language_query(&query);
-        
+
+        // Authorize the resolved subject against the target collection
+        let subject = self.subject_for_token(&auth_token).await;
+        self.enforce(&subject, &search_params.collection, "read").await?;
+
+        // Serve a cached formatted result for identical repeated prompts before
+        // touching the database.
+        let key = cache_key(&search_params.collection, &query);
+        if let Some(cached) = self.cache.get(&key).await {
+            metrics().record_cache_hit();
+            return Ok(cached);
+        }
+        metrics().record_cache_miss();
+
         // Execute the appropriate search based on collection type
+        let started = std::time::Instant::now();
         let db_response = match search_params.collection.as_str() {
             "novels" => self.db_service.as_ref().search_novels(&search_params).await,
             "chapters" => self.db_service.as_ref().search_chapters(&search_params).await,
@@ -219,6 +279,7 @@ impl<T: DatabaseService + Clone + Send + Sync + 'static> MCPDatabaseServer<T> {
                 return Err(format!("Unknown collection type: {}", search_params.collection));
             }
         };
+        metrics().record_db_latency(started.elapsed());
         
         // Handle database errors
         let db_result = match db_response {
@@ -230,12 +291,23 @@ impl<T: DatabaseService + Clone + Send + Sync + 'static> MCPDatabaseServer<T> {
         
         // Format result for LLM consumption
         let content = formatting::format_content_for_llm(&db_result.data, &search_params);
-        
+
+        // Cache the formatted result for subsequent identical prompts.
+        self.cache.set(&key, &content).await;
+
         Ok(content)
     }
     
     #[tool(description = "Retrieve specific chapter content by ID")]
-    pub async fn get_chapter_content(&self, #[tool(param)] chapter_id: String) -> Result<String, String> {
+    pub async fn get_chapter_content(
+        &self,
+        #[tool(param)] chapter_id: String,
+        #[tool(param)] auth_token: String,
+    ) -> Result<String, String> {
+        metrics().record_tool_call("get_chapter_content");
+        let subject = self.subject_for_token(&auth_token).await;
+        self.enforce(&subject, "chapters", "read").await?;
+
         // Cast to the concrete type MongoDBService that we know implements these methods
         // This is a workaround for the trait not having these methods
         let db_service = self.db_service.as_ref();
@@ -252,7 +324,15 @@ impl<T: DatabaseService + Clone + Send + Sync + 'static> MCPDatabaseServer<T> {
     }
     
     #[tool(description = "Retrieve detailed character information by ID")]
-    pub async fn get_character_details(&self, #[tool(param)] character_id: String) -> Result<String, String> {
+    pub async fn get_character_details(
+        &self,
+        #[tool(param)] character_id: String,
+        #[tool(param)] auth_token: String,
+    ) -> Result<String, String> {
+        metrics().record_tool_call("get_character_details");
+        let subject = self.subject_for_token(&auth_token).await;
+        self.enforce(&subject, "characters", "read").await?;
+
         // Cast to the concrete type MongoDBService that we know implements these methods
         // This is a workaround for the trait not having these methods
         let db_service = self.db_service.as_ref();
@@ -269,29 +349,77 @@ impl<T: DatabaseService + Clone + Send + Sync + 'static> MCPDatabaseServer<T> {
     }
     
     #[tool(description = "Search Q&A entries using regex pattern")]
-    pub async fn query_qa_regex(&self, #[tool(param)] regex_pattern: String) -> Result<String, String> {
+    pub async fn query_qa_regex(
+        &self,
+        #[tool(param)] regex_pattern: String,
+        #[tool(param)] auth_token: String,
+    ) -> Result<String, String> {
+        let subject = self.subject_for_token(&auth_token).await;
+        metrics().record_tool_call("query_qa_regex");
+        self.enforce(&subject, "qa", "read").await?;
+
+        let key = cache_key("qa", &regex_pattern);
+        if let Some(cached) = self.cache.get(&key).await {
+            metrics().record_cache_hit();
+            return Ok(cached);
+        }
+        metrics().record_cache_miss();
+
         let qa_entries = self.db_service.as_ref().search_qa_by_regex(&regex_pattern).await
             .map_err(|e| format!("Failed to search Q&A entries: {}", e))?;
-            
+
         let formatted = formatting::format_qa(&qa_entries);
+        self.cache.set(&key, &formatted).await;
         Ok(formatted)
     }
     
     #[tool(description = "Search chapters using regex pattern")]
-    pub async fn query_chapter_regex(&self, #[tool(param)] regex_pattern: String) -> Result<String, String> {
+    pub async fn query_chapter_regex(
+        &self,
+        #[tool(param)] regex_pattern: String,
+        #[tool(param)] auth_token: String,
+    ) -> Result<String, String> {
+        let subject = self.subject_for_token(&auth_token).await;
+        metrics().record_tool_call("query_chapter_regex");
+        self.enforce(&subject, "chapters", "read").await?;
+
+        let key = cache_key("chapters", &regex_pattern);
+        if let Some(cached) = self.cache.get(&key).await {
+            metrics().record_cache_hit();
+            return Ok(cached);
+        }
+        metrics().record_cache_miss();
+
         let chapters = self.db_service.as_ref().search_chapters_by_regex(&regex_pattern).await
             .map_err(|e| format!("Failed to search chapters: {}", e))?;
-            
+
         let formatted = formatting::format_chapters(&chapters);
+        self.cache.set(&key, &formatted).await;
         Ok(formatted)
     }
     
     #[tool(description = "Search characters using regex pattern")]
-    pub async fn query_character_regex(&self, #[tool(param)] regex_pattern: String) -> Result<String, String> {
+    pub async fn query_character_regex(
+        &self,
+        #[tool(param)] regex_pattern: String,
+        #[tool(param)] auth_token: String,
+    ) -> Result<String, String> {
+        let subject = self.subject_for_token(&auth_token).await;
+        metrics().record_tool_call("query_character_regex");
+        self.enforce(&subject, "characters", "read").await?;
+
+        let key = cache_key("characters", &regex_pattern);
+        if let Some(cached) = self.cache.get(&key).await {
+            metrics().record_cache_hit();
+            return Ok(cached);
+        }
+        metrics().record_cache_miss();
+
         let characters = self.db_service.as_ref().search_characters_by_regex(&regex_pattern).await
             .map_err(|e| format!("Failed to search characters: {}", e))?;
-            
+
         let formatted = formatting::format_characters(&characters);
+        self.cache.set(&key, &formatted).await;
         Ok(formatted)
     }
     
@@ -303,17 +431,23 @@ impl<T: DatabaseService + Clone + Send + Sync + 'static> MCPDatabaseServer<T> {
         #[tool(param)] summary: String, 
         #[tool(param)] auth_token: String
     ) -> Result<String, String> {
-        // Validate authentication token
-        if auth_token != "trusted_llm_token" {
-            return Err("Invalid or missing authentication token".to_string());
-        }
-        
+        metrics().record_tool_call("update_chapter_summary");
+        // Authorize the resolved subject for write access to chapters
+        let subject = self.subject_for_token(&auth_token).await;
+        self.enforce(&subject, "chapters", "write").await?;
+
         // Update the chapter summary in the database
         self.db_service.as_ref()
             .update_chapter_summary(&chapter_id, &summary)
             .await
             .map_err(|e| format!("Failed to update chapter summary: {}", e))?;
-        
+
+        // Drop the cached formatted result for this chapter's exact-id lookup so
+        // the edited summary isn't served stale from the read path.
+        self.cache
+            .invalidate(&cache_key("chapters", &format!("^{}$", chapter_id)))
+            .await;
+
         Ok("Chapter summary updated successfully".to_string())
     }
 }
@@ -321,114 +455,60 @@ impl<T: DatabaseService + Clone + Send + Sync + 'static> MCPDatabaseServer<T> {
 // Implement direct methods that can be called from our HTTP handler
 impl<T: DatabaseService + Clone + Send + Sync + 'static> MCPDatabaseServer<T> {
     // This method handles direct query requests from HTTP handler
-    pub async fn handle_query(&self, query: &str) -> Result<CallToolResult, Error> {
-        match self.query(query.to_string()).await {
-            Ok(content) => {
-                Ok(CallToolResult {
-                    content: vec![Annotated::new(RawContent::text(content), None)],
-                    is_error: None,
-                })
-            },
-            Err(e) => {
-                Err(Error::invalid_params(e, None))
-            }
-        }
+    pub async fn handle_query(&self, query: &str, auth_token: &str) -> Result<CallToolResult, Error> {
+        tool_result!("query", self.query(query.to_string(), auth_token.to_string()).await)
     }
-    
+
     // Direct method for chapter content
-    pub async fn handle_chapter_content(&self, chapter_id: &str) -> Result<CallToolResult, Error> {
-        // Reuse our existing tool implementation
-        match self.get_chapter_content(chapter_id.to_string()).await {
-            Ok(content) => {
-                Ok(CallToolResult {
-                    content: vec![Annotated::new(RawContent::text(content), None)],
-                    is_error: None,
-                })
-            },
-            Err(e) => {
-                Err(Error::invalid_params(e, None))
-            }
-        }
+    pub async fn handle_chapter_content(&self, chapter_id: &str, auth_token: &str) -> Result<CallToolResult, Error> {
+        tool_result!(
+            "get_chapter_content",
+            self.get_chapter_content(chapter_id.to_string(), auth_token.to_string()).await
+        )
     }
-    
+
     // Direct method for character details
-    pub async fn handle_character_details(&self, character_id: &str) -> Result<CallToolResult, Error> {
-        // Reuse our existing tool implementation
-        match self.get_character_details(character_id.to_string()).await {
-            Ok(content) => {
-                Ok(CallToolResult {
-                    content: vec![Annotated::new(RawContent::text(content), None)],
-                    is_error: None,
-                })
-            },
-            Err(e) => {
-                Err(Error::invalid_params(e, None))
-            }
-        }
+    pub async fn handle_character_details(&self, character_id: &str, auth_token: &str) -> Result<CallToolResult, Error> {
+        tool_result!(
+            "get_character_details",
+            self.get_character_details(character_id.to_string(), auth_token.to_string()).await
+        )
     }
-    
+
     // Direct method for Q&A regex
-    pub async fn handle_qa_regex(&self, regex: &str) -> Result<CallToolResult, Error> {
-        match self.query_qa_regex(regex.to_string()).await {
-            Ok(content) => {
-                Ok(CallToolResult {
-                    content: vec![Annotated::new(RawContent::text(content), None)],
-                    is_error: None,
-                })
-            },
-            Err(e) => {
-                Err(Error::invalid_params(e, None))
-            }
-        }
+    pub async fn handle_qa_regex(&self, regex: &str, auth_token: &str) -> Result<CallToolResult, Error> {
+        tool_result!(
+            "query_qa_regex",
+            self.query_qa_regex(regex.to_string(), auth_token.to_string()).await
+        )
     }
-    
+
     // Direct method for chapter regex
-    pub async fn handle_chapter_regex(&self, regex: &str) -> Result<CallToolResult, Error> {
-        match self.query_chapter_regex(regex.to_string()).await {
-            Ok(content) => {
-                Ok(CallToolResult {
-                    content: vec![Annotated::new(RawContent::text(content), None)],
-                    is_error: None,
-                })
-            },
-            Err(e) => {
-                Err(Error::invalid_params(e, None))
-            }
-        }
+    pub async fn handle_chapter_regex(&self, regex: &str, auth_token: &str) -> Result<CallToolResult, Error> {
+        tool_result!(
+            "query_chapter_regex",
+            self.query_chapter_regex(regex.to_string(), auth_token.to_string()).await
+        )
     }
-    
+
     // Direct method for character regex
-    pub async fn handle_character_regex(&self, regex: &str) -> Result<CallToolResult, Error> {
-        match self.query_character_regex(regex.to_string()).await {
-            Ok(content) => {
-                Ok(CallToolResult {
-                    content: vec![Annotated::new(RawContent::text(content), None)],
-                    is_error: None,
-                })
-            },
-            Err(e) => {
-                Err(Error::invalid_params(e, None))
-            }
-        }
+    pub async fn handle_character_regex(&self, regex: &str, auth_token: &str) -> Result<CallToolResult, Error> {
+        tool_result!(
+            "query_character_regex",
+            self.query_character_regex(regex.to_string(), auth_token.to_string()).await
+        )
     }
-    
+
     // Direct method for updating chapter summary
     pub async fn handle_chapter_summary_update(&self, chapter_id: &str, summary: &str) -> Result<CallToolResult, Error> {
         #[cfg(feature = "mcp_write_access")]
         {
             // Assuming a default token for simplicity - in production would use proper auth
             let auth_token = "trusted_llm_token".to_string();
-            match self.update_chapter_summary(chapter_id.to_string(), summary.to_string(), auth_token).await {
-                Ok(content) => {
-                    Ok(CallToolResult {
-                        content: vec![Annotated::new(RawContent::text(content), None)],
-                        is_error: None,
-                    })
-                },
-                Err(e) => {
-                    Err(Error::invalid_params(e, None))
-                }
-            }
+            tool_result!(
+                "update_chapter_summary",
+                self.update_chapter_summary(chapter_id.to_string(), summary.to_string(), auth_token).await
+            )
         }
         
         #[cfg(not(feature = "mcp_write_access"))]
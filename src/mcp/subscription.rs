@@ -0,0 +1,131 @@
+//! Live-update subscriptions for the MCP server.
+//!
+//! A client subscribes with a declarative [`ReqFilter`]; the server stores it
+//! in a [`Registry`] under a generated id and, on each newly inserted or
+//! updated document, emits an SSE event to the matching subscriptions. Every
+//! optional field is ignored when `None`; a present field matches when *all*
+//! its constraints hold (AND across fields), and within a set field any member
+//! matching is enough (OR within a field). A malformed filter deserializes into
+//! [`ReqFilter::no_match`] via [`ReqFilter::from_value`] so a bad request
+//! simply matches nothing rather than erroring.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::Mutex;
+
+/// A declarative subscription filter. Each optional field, when present,
+/// narrows the matched documents; `None` fields are ignored.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReqFilter {
+    #[serde(default)]
+    pub ids: Option<Vec<String>>,
+    /// Collection names to watch: `novels`, `chapters`, `characters`, `qa`.
+    #[serde(default)]
+    pub collections: Option<Vec<String>>,
+    #[serde(default)]
+    pub authors: Option<Vec<String>>,
+    /// Inclusive lower bound on a document's `created_at` timestamp.
+    #[serde(default)]
+    pub since: Option<i64>,
+    /// Inclusive upper bound on a document's `created_at` timestamp.
+    #[serde(default)]
+    pub until: Option<i64>,
+    /// Cap on the initial backfill size.
+    #[serde(default)]
+    pub limit: Option<usize>,
+    /// Tag constraints keyed by category: a document must carry, for every
+    /// category, at least one of the category's tags.
+    #[serde(default)]
+    pub tags: Option<HashMap<char, HashSet<String>>>,
+    /// When set, the filter matches nothing. Used as the safe fallback for a
+    /// filter that failed to parse.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub force_no_match: bool,
+}
+
+fn is_false(b: &bool) -> bool {
+    !*b
+}
+
+impl ReqFilter {
+    /// A filter that matches nothing, used when a request's filter is malformed.
+    pub fn no_match() -> Self {
+        Self {
+            force_no_match: true,
+            ..Self::default()
+        }
+    }
+
+    /// Parse a filter from raw tool arguments, falling back to [`no_match`](Self::no_match)
+    /// rather than erroring when the payload is malformed.
+    pub fn from_value(value: Value) -> Self {
+        serde_json::from_value(value).unwrap_or_else(|_| Self::no_match())
+    }
+
+    /// Whether `document` from `collection` satisfies every present field.
+    pub fn matches(&self, collection: &str, document: &Value) -> bool {
+        if self.force_no_match {
+            return false;
+        }
+
+        if let Some(collections) = &self.collections {
+            if !collections.iter().any(|c| c == collection) {
+                return false;
+            }
+        }
+
+        if let Some(ids) = &self.ids {
+            let id = document.get("_id").and_then(|v| v.as_str()).unwrap_or_default();
+            if !ids.iter().any(|candidate| candidate == id) {
+                return false;
+            }
+        }
+
+        if let Some(authors) = &self.authors {
+            let author = document.get("author").and_then(|v| v.as_str()).unwrap_or_default();
+            if !authors.iter().any(|candidate| candidate == author) {
+                return false;
+            }
+        }
+
+        if let Some(since) = self.since {
+            let created = document.get("created_at").and_then(|v| v.as_i64()).unwrap_or(i64::MIN);
+            if created < since {
+                return false;
+            }
+        }
+
+        if let Some(until) = self.until {
+            let created = document.get("created_at").and_then(|v| v.as_i64()).unwrap_or(i64::MAX);
+            if created > until {
+                return false;
+            }
+        }
+
+        if let Some(tag_sets) = &self.tags {
+            let doc_tags: HashSet<&str> = document
+                .get("tags")
+                .and_then(|v| v.as_array())
+                .map(|arr| arr.iter().filter_map(|t| t.as_str()).collect())
+                .unwrap_or_default();
+            for wanted in tag_sets.values() {
+                if !wanted.iter().any(|tag| doc_tags.contains(tag.as_str())) {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+}
+
+/// Shared registry mapping a subscription id to its filter.
+pub type Registry = Arc<Mutex<HashMap<String, ReqFilter>>>;
+
+/// Build an empty subscription registry.
+pub fn new_registry() -> Registry {
+    Arc::new(Mutex::new(HashMap::new()))
+}
@@ -0,0 +1,162 @@
+//! A small declarative builder for MCP tool input schemas.
+//!
+//! `list_tools` used to hand-assemble each tool's JSON Schema out of nested
+//! `serde_json::Map`s — dozens of stringly-typed lines per tool. [`ToolSchema`]
+//! collects field definitions once and emits both the `Arc<Map>` the
+//! [`Tool`](rmcp::model::Tool) struct expects (via [`build`](ToolSchema::build))
+//! and a reusable validator ([`validate`](ToolSchema::validate)) so `call_tool`
+//! can reject missing or out-of-range arguments before dispatch instead of
+//! silently falling back to defaults.
+
+use std::sync::Arc;
+
+use serde_json::{json, Map, Value};
+
+/// The kind of a declared field, carrying the constraints needed both to emit
+/// the schema and to validate incoming values.
+enum FieldType {
+    Str,
+    EnumStr(Vec<String>),
+    Integer { min: Option<i64>, max: Option<i64> },
+}
+
+struct Field {
+    name: String,
+    description: String,
+    ty: FieldType,
+    required: bool,
+}
+
+/// A declarative tool input schema: a set of typed fields that renders to JSON
+/// Schema and validates/coerces argument maps.
+#[derive(Default)]
+pub struct ToolSchema {
+    fields: Vec<Field>,
+}
+
+impl ToolSchema {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A free-text string field.
+    pub fn string(mut self, name: &str, description: &str, required: bool) -> Self {
+        self.fields.push(Field {
+            name: name.to_string(),
+            description: description.to_string(),
+            ty: FieldType::Str,
+            required,
+        });
+        self
+    }
+
+    /// A string field constrained to a fixed set of variants.
+    pub fn enum_str(mut self, name: &str, description: &str, variants: &[&str], required: bool) -> Self {
+        self.fields.push(Field {
+            name: name.to_string(),
+            description: description.to_string(),
+            ty: FieldType::EnumStr(variants.iter().map(|v| v.to_string()).collect()),
+            required,
+        });
+        self
+    }
+
+    /// An optional integer field with inclusive bounds; numeric strings are
+    /// coerced to integers during validation.
+    pub fn integer(mut self, name: &str, description: &str, min: Option<i64>, max: Option<i64>) -> Self {
+        self.fields.push(Field {
+            name: name.to_string(),
+            description: description.to_string(),
+            ty: FieldType::Integer { min, max },
+            required: false,
+        });
+        self
+    }
+
+    /// Render the schema as the `Arc<Map>` a [`Tool`](rmcp::model::Tool) expects.
+    pub fn build(&self) -> Arc<Map<String, Value>> {
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        for field in &self.fields {
+            let mut prop = Map::new();
+            match &field.ty {
+                FieldType::Str => {
+                    prop.insert("type".to_string(), json!("string"));
+                }
+                FieldType::EnumStr(variants) => {
+                    prop.insert("type".to_string(), json!("string"));
+                    prop.insert("enum".to_string(), json!(variants));
+                }
+                FieldType::Integer { min, max } => {
+                    prop.insert("type".to_string(), json!("integer"));
+                    if let Some(min) = min {
+                        prop.insert("minimum".to_string(), json!(min));
+                    }
+                    if let Some(max) = max {
+                        prop.insert("maximum".to_string(), json!(max));
+                    }
+                }
+            }
+            prop.insert("description".to_string(), json!(field.description));
+            properties.insert(field.name.clone(), Value::Object(prop));
+            if field.required {
+                required.push(Value::String(field.name.clone()));
+            }
+        }
+
+        let mut schema = Map::new();
+        schema.insert("type".to_string(), json!("object"));
+        schema.insert("properties".to_string(), Value::Object(properties));
+        schema.insert("required".to_string(), Value::Array(required));
+        Arc::new(schema)
+    }
+
+    /// Validate and coerce an argument map in place. Rejects missing required
+    /// fields, enum values outside the allowed set, and integers out of range;
+    /// coerces numeric strings to integers. The error names the offending field.
+    pub fn validate(&self, args: &mut Map<String, Value>) -> Result<(), String> {
+        for field in &self.fields {
+            let Some(value) = args.get(&field.name) else {
+                if field.required {
+                    return Err(format!("missing required parameter '{}'", field.name));
+                }
+                continue;
+            };
+
+            match &field.ty {
+                FieldType::Str => {
+                    if !value.is_string() {
+                        return Err(format!("parameter '{}' must be a string", field.name));
+                    }
+                }
+                FieldType::EnumStr(variants) => {
+                    let ok = value.as_str().is_some_and(|v| variants.iter().any(|allowed| allowed == v));
+                    if !ok {
+                        return Err(format!(
+                            "parameter '{}' must be one of [{}]",
+                            field.name,
+                            variants.join(", ")
+                        ));
+                    }
+                }
+                FieldType::Integer { min, max } => {
+                    // Coerce a numeric string to an integer so callers that pass
+                    // "3" still satisfy the schema.
+                    let coerced = match value {
+                        Value::Number(n) => n.as_i64(),
+                        Value::String(s) => s.parse::<i64>().ok(),
+                        _ => None,
+                    };
+                    let Some(n) = coerced else {
+                        return Err(format!("parameter '{}' must be an integer", field.name));
+                    };
+                    if min.is_some_and(|m| n < m) || max.is_some_and(|m| n > m) {
+                        return Err(format!("parameter '{}' is out of range", field.name));
+                    }
+                    args.insert(field.name.clone(), json!(n));
+                }
+            }
+        }
+        Ok(())
+    }
+}
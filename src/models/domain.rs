@@ -1,10 +1,15 @@
 use serde::{Deserialize, Serialize};
 use mongodb::bson::oid::ObjectId;
+use std::fmt;
+use utoipa::ToSchema;
 
 // Novel metadata - compact representation
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct Novel {
+    // `ObjectId` has no JSON-schema representation, so surface it to OpenAPI as
+    // the hex string clients actually send and receive.
     #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
     pub id: Option<ObjectId>,
     pub title: String,
     pub author: String,
@@ -12,10 +17,15 @@ pub struct Novel {
     pub tags: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<NovelMetadata>,
+    /// Id of the authenticated user that created the novel; mutations require
+    /// the caller to match it. Never echoed back to clients — it's an
+    /// ownership check input, not response data.
+    #[serde(default, skip_serializing)]
+    pub owner_id: Option<String>,
 }
 
 // Extended metadata separated to keep main queries light
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct NovelMetadata {
     pub publication_date: Option<String>,
     pub genre: Vec<String>,
@@ -24,10 +34,12 @@ pub struct NovelMetadata {
 }
 
 // Chapters - optimized structure with summary and key points
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct Chapter {
     #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
     pub id: Option<ObjectId>,
+    #[schema(value_type = String)]
     pub novel_id: ObjectId,
     pub number: u32,
     pub title: String,
@@ -35,37 +47,48 @@ pub struct Chapter {
     pub key_points: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub content: Option<String>, // Full content stored separately
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<Vec<f32>>, // Semantic search vector
 }
 
 // Characters - focus on key attributes and relationships
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct Character {
     #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
     pub id: Option<ObjectId>,
+    #[schema(value_type = String)]
     pub novel_id: ObjectId,
     pub name: String,
     pub role: String, // protagonist, antagonist, supporting
     pub description: String,
     pub key_traits: Vec<String>,
     pub relationships: Vec<Relationship>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<Vec<f32>>, // Semantic search vector
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct Relationship {
+    #[schema(value_type = Option<String>)]
     pub character_id: Option<ObjectId>,
     pub character_name: String, // Denormalized for efficiency
     pub relationship_type: String, // friend, enemy, family, etc.
 }
 
 // Q&A - knowledge base entries
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct QA {
     #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
     pub id: Option<ObjectId>,
+    #[schema(value_type = Option<String>)]
     pub novel_id: Option<ObjectId>,
     pub question: String,
     pub answer: String,
     pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<Vec<f32>>, // Semantic search vector
 }
 
 // Search query parameters - used for MCP requests
@@ -76,13 +99,52 @@ pub struct SearchParams {
     pub keywords: Vec<String>,
     pub filters: Option<SearchFilters>,
     pub limit: Option<u32>,
+    /// Opaque keyset cursor from a previous page's `next_page_token`.
+    #[serde(default)]
+    pub page_token: Option<String>,
+    /// Compact filter expression merged into every collection's query, e.g.
+    /// `number >= 10 AND number <= 20 AND tags IN [war, betrayal]`.
+    #[serde(default)]
+    pub filter_expr: Option<String>,
+    /// Validated Mongo filter emitted by the [`FilterBuilder`] AST (via
+    /// `QueryParser::build_filter`), merged into the collection's query. Built
+    /// from the comparison clauses recognised in a natural-language query.
+    ///
+    /// [`FilterBuilder`]: crate::utils::filter_builder::FilterBuilder
+    #[serde(default)]
+    pub compiled_filter: Option<mongodb::bson::Document>,
+    /// The raw query string, retained so the search methods can build the
+    /// typo-tolerant boolean [`query_tree`] when the query uses `AND`/`OR`,
+    /// parentheses, or quoted phrases.
+    ///
+    /// [`query_tree`]: crate::utils::query_tree
+    #[serde(default)]
+    pub text_query: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct SearchFilters {
     pub novel_id: Option<String>,
     pub character_name: Option<String>,
     pub tags: Option<Vec<String>>,
+    /// Match any of several ids (`_id $in`).
+    #[serde(default)]
+    pub ids: Option<Vec<String>>,
+    /// Inclusive chapter-number lower bound (`number $gte`).
+    #[serde(default)]
+    pub number_gte: Option<i64>,
+    /// Inclusive chapter-number upper bound (`number $lte`).
+    #[serde(default)]
+    pub number_lte: Option<i64>,
+    /// Lower bound on the `created_at` date field.
+    #[serde(default)]
+    pub since: Option<String>,
+    /// Upper bound on the `created_at` date field.
+    #[serde(default)]
+    pub until: Option<String>,
+    /// Exclude documents carrying any of these tags (`tags $nin`).
+    #[serde(default)]
+    pub not_tags: Option<Vec<String>>,
 }
 
 // MCP response - optimized for small context windows
@@ -99,4 +161,121 @@ pub struct ResponseMetadata {
     pub query_time_ms: u64,
     pub has_more: bool,
     pub next_page_token: Option<String>,
+    /// Encoding applied to the payload when it was compressed (e.g. `"gzip"`),
+    /// absent when the body is sent as plain JSON.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_encoding: Option<String>,
+    /// Serialized size of the content before compression, in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uncompressed_bytes: Option<usize>,
+    /// Size of the content actually sent, in bytes; equals
+    /// `uncompressed_bytes` when the body was not compressed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compressed_bytes: Option<usize>,
+}
+
+// Structured, machine-readable failures that mirror the MCPResponse success
+// shape. Callers branch on `code` instead of parsing opaque error strings.
+#[derive(Debug, Clone, PartialEq)]
+pub enum McpError {
+    /// A supplied id was not a valid ObjectId.
+    InvalidId(String),
+    /// The requested document does not exist.
+    NotFound(String),
+    /// A filter expression could not be parsed or validated.
+    InvalidFilter(String),
+    /// The query carried no searchable terms.
+    EmptyQuery,
+    /// A regular expression could not be compiled.
+    InvalidRegex(String),
+    /// A document failed to (de)serialize.
+    Serialization(String),
+    /// The database could not be reached or a query failed.
+    DbUnavailable(String),
+}
+
+impl McpError {
+    /// Stable, machine-readable code for the error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            McpError::InvalidId(_) => "invalid_id",
+            McpError::NotFound(_) => "not_found",
+            McpError::InvalidFilter(_) => "invalid_filter",
+            McpError::EmptyQuery => "empty_query",
+            McpError::InvalidRegex(_) => "invalid_regex",
+            McpError::Serialization(_) => "serialization_error",
+            McpError::DbUnavailable(_) => "db_unavailable",
+        }
+    }
+
+    /// HTTP-like status associated with the error.
+    pub fn status(&self) -> u16 {
+        match self {
+            McpError::InvalidId(_)
+            | McpError::InvalidFilter(_)
+            | McpError::EmptyQuery
+            | McpError::InvalidRegex(_) => 400,
+            McpError::NotFound(_) => 404,
+            McpError::Serialization(_) | McpError::DbUnavailable(_) => 503,
+        }
+    }
+
+    /// Human-readable message.
+    pub fn message(&self) -> String {
+        match self {
+            McpError::InvalidId(id) => format!("'{}' is not a valid id", id),
+            McpError::NotFound(what) => format!("{} not found", what),
+            McpError::InvalidFilter(reason) => format!("invalid filter: {}", reason),
+            McpError::EmptyQuery => "query has no searchable terms".to_string(),
+            McpError::InvalidRegex(reason) => format!("invalid regular expression: {}", reason),
+            McpError::Serialization(reason) => format!("serialization failed: {}", reason),
+            McpError::DbUnavailable(reason) => format!("database unavailable: {}", reason),
+        }
+    }
+
+    /// Recover a typed error from an [`anyhow::Error`], defaulting to
+    /// `db_unavailable` for failures that did not originate as an [`McpError`].
+    pub fn from_anyhow(err: &anyhow::Error) -> McpError {
+        match err.downcast_ref::<McpError>() {
+            Some(error) => error.clone(),
+            None => McpError::DbUnavailable(err.to_string()),
+        }
+    }
+
+    /// Render the structured error body analogous to the success response.
+    pub fn to_response(&self) -> serde_json::Value {
+        serde_json::json!({
+            "status": "error",
+            "code": self.code(),
+            "message": self.message(),
+        })
+    }
+}
+
+impl fmt::Display for McpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+impl std::error::Error for McpError {}
+
+// `?`-propagation from the underlying libraries, so a typed error flows through
+// one conversion point instead of collapsing into an opaque internal failure.
+impl From<regex::Error> for McpError {
+    fn from(err: regex::Error) -> Self {
+        McpError::InvalidRegex(err.to_string())
+    }
+}
+
+impl From<serde_json::Error> for McpError {
+    fn from(err: serde_json::Error) -> Self {
+        McpError::Serialization(err.to_string())
+    }
+}
+
+impl From<mongodb::error::Error> for McpError {
+    fn from(err: mongodb::error::Error) -> Self {
+        McpError::DbUnavailable(err.to_string())
+    }
 }
\ No newline at end of file
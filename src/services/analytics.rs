@@ -0,0 +1,168 @@
+//! Aggregation-pipeline analytics over the novel collections.
+//!
+//! The CRUD services answer "give me this document"; this service answers "what
+//! do the documents look like in aggregate". Each view builds a
+//! `$group`/`$sort`/`$project` pipeline through [`collection.aggregate`] and
+//! deserializes the result rows into small stat structs rather than handing raw
+//! [`Document`]s back to callers.
+//!
+//! Because the same MCP summary prompt tends to repeat, the most recent result
+//! of each view is cached by a hash of its pipeline so repeated requests skip the
+//! collection scan.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+
+use anyhow::Result;
+use futures::TryStreamExt;
+use mongodb::bson::{doc, oid::ObjectId, Document};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::db::DatabaseConnection;
+
+/// One row of [`AnalyticsService::chapters_per_novel`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChaptersPerNovel {
+    #[serde(rename = "_id")]
+    pub novel_id: ObjectId,
+    pub chapters: u64,
+}
+
+/// One row of [`AnalyticsService::tag_histogram`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagCount {
+    #[serde(rename = "_id")]
+    pub tag: String,
+    pub count: u64,
+}
+
+/// One row of [`AnalyticsService::character_role_counts`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoleCount {
+    #[serde(rename = "_id")]
+    pub role: String,
+    pub count: u64,
+}
+
+/// One row of [`AnalyticsService::word_count_over_chapters`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChapterWordCount {
+    pub number: u32,
+    pub word_count: u64,
+}
+
+/// Aggregation-backed statistics over the novel collections.
+pub struct AnalyticsService {
+    db: DatabaseConnection,
+    /// Most recent result rows per view, keyed by pipeline hash.
+    cache: Mutex<HashMap<u64, Vec<Document>>>,
+}
+
+impl AnalyticsService {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self {
+            db,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Run `pipeline` against `collection`, deserializing each result row into
+    /// `R`. The raw rows are cached by pipeline hash so an identical view served
+    /// again skips the scan.
+    pub async fn run_view<R: DeserializeOwned>(
+        &self,
+        collection: &str,
+        pipeline: Vec<Document>,
+    ) -> Result<Vec<R>> {
+        let key = pipeline_hash(&pipeline);
+
+        if let Some(rows) = self.cached_rows(key) {
+            return decode_rows(rows);
+        }
+
+        let coll = self.db.get_collection::<Document>(collection);
+        let mut cursor = coll.aggregate(pipeline, None).await?;
+        let mut rows = Vec::new();
+        while let Some(document) = cursor.try_next().await? {
+            rows.push(document);
+        }
+
+        self.store_rows(key, rows.clone());
+        decode_rows(rows)
+    }
+
+    /// Number of chapters grouped under each novel, most prolific first.
+    pub async fn chapters_per_novel(&self) -> Result<Vec<ChaptersPerNovel>> {
+        let pipeline = vec![
+            doc! { "$group": { "_id": "$novel_id", "chapters": { "$sum": 1 } } },
+            doc! { "$sort": { "chapters": -1 } },
+        ];
+        self.run_view("chapters", pipeline).await
+    }
+
+    /// Frequency of each tag across all novels, most common first.
+    pub async fn tag_histogram(&self) -> Result<Vec<TagCount>> {
+        let pipeline = vec![
+            doc! { "$unwind": "$tags" },
+            doc! { "$group": { "_id": "$tags", "count": { "$sum": 1 } } },
+            doc! { "$sort": { "count": -1, "_id": 1 } },
+        ];
+        self.run_view("novels", pipeline).await
+    }
+
+    /// Number of characters per role within a single novel.
+    pub async fn character_role_counts(&self, novel_id: ObjectId) -> Result<Vec<RoleCount>> {
+        let pipeline = vec![
+            doc! { "$match": { "novel_id": novel_id } },
+            doc! { "$group": { "_id": "$role", "count": { "$sum": 1 } } },
+            doc! { "$sort": { "count": -1, "_id": 1 } },
+        ];
+        self.run_view("characters", pipeline).await
+    }
+
+    /// Word count of each chapter's content (its summary when the chapter has
+    /// no content) within a novel, in chapter order.
+    pub async fn word_count_over_chapters(&self, novel_id: ObjectId) -> Result<Vec<ChapterWordCount>> {
+        let pipeline = vec![
+            doc! { "$match": { "novel_id": novel_id } },
+            doc! {
+                "$project": {
+                    "_id": 0,
+                    "number": 1,
+                    "word_count": {
+                        "$size": {
+                            "$split": [{ "$ifNull": ["$content", "$summary"] }, " "]
+                        }
+                    }
+                }
+            },
+            doc! { "$sort": { "number": 1 } },
+        ];
+        self.run_view("chapters", pipeline).await
+    }
+
+    fn cached_rows(&self, key: u64) -> Option<Vec<Document>> {
+        self.cache.lock().unwrap().get(&key).cloned()
+    }
+
+    fn store_rows(&self, key: u64, rows: Vec<Document>) {
+        self.cache.lock().unwrap().insert(key, rows);
+    }
+}
+
+/// Deserialize cached raw rows into the caller's stat struct.
+fn decode_rows<R: DeserializeOwned>(rows: Vec<Document>) -> Result<Vec<R>> {
+    rows.into_iter()
+        .map(|document| Ok(mongodb::bson::from_document(document)?))
+        .collect()
+}
+
+/// Stable hash of a pipeline, used as the per-view cache key.
+fn pipeline_hash(pipeline: &[Document]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    format!("{:?}", pipeline).hash(&mut hasher);
+    hasher.finish()
+}
@@ -0,0 +1,111 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+
+use lru::LruCache;
+
+/// Pluggable cache for formatted query results. Implementations key formatted
+/// LLM output by a stable string derived from the collection plus the
+/// normalized query/regex, so identical repeated prompts skip the DB search and
+/// the `format_content_for_llm` pass.
+#[async_trait]
+pub trait ResultCache: Send + Sync {
+    async fn get(&self, key: &str) -> Option<String>;
+    async fn set(&self, key: &str, value: &str);
+    async fn invalidate(&self, key: &str);
+}
+
+/// Compute a cache key from the target collection and a query/regex string. The
+/// query is lowercased and whitespace-collapsed so trivially different phrasings
+/// of the same prompt hit the same entry.
+pub fn cache_key(collection: &str, query: &str) -> String {
+    let normalized = query.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    format!("{}:{}", collection, normalized)
+}
+
+/// Redis-backed cache. The connection URL comes from the environment; entries
+/// expire after a configurable TTL.
+pub struct RedisCache {
+    client: redis::Client,
+    ttl_secs: u64,
+}
+
+impl RedisCache {
+    pub fn new(url: &str, ttl_secs: u64) -> Result<Self> {
+        Ok(Self {
+            client: redis::Client::open(url)?,
+            ttl_secs,
+        })
+    }
+}
+
+#[async_trait]
+impl ResultCache for RedisCache {
+    async fn get(&self, key: &str) -> Option<String> {
+        let mut conn = self.client.get_async_connection().await.ok()?;
+        redis::cmd("GET").arg(key).query_async(&mut conn).await.ok()
+    }
+
+    async fn set(&self, key: &str, value: &str) {
+        if let Ok(mut conn) = self.client.get_async_connection().await {
+            let _: Result<(), _> = redis::cmd("SET")
+                .arg(key)
+                .arg(value)
+                .arg("EX")
+                .arg(self.ttl_secs)
+                .query_async(&mut conn)
+                .await;
+        }
+    }
+
+    async fn invalidate(&self, key: &str) {
+        if let Ok(mut conn) = self.client.get_async_connection().await {
+            let _: Result<(), _> = redis::cmd("DEL").arg(key).query_async(&mut conn).await;
+        }
+    }
+}
+
+/// In-memory LRU fallback used when Redis is not configured.
+pub struct InMemoryCache {
+    inner: Mutex<LruCache<String, String>>,
+}
+
+impl InMemoryCache {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity.max(1)).unwrap();
+        Self {
+            inner: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+}
+
+#[async_trait]
+impl ResultCache for InMemoryCache {
+    async fn get(&self, key: &str) -> Option<String> {
+        let mut cache = self.inner.lock().unwrap();
+        cache.get(key).cloned()
+    }
+
+    async fn set(&self, key: &str, value: &str) {
+        let mut cache = self.inner.lock().unwrap();
+        cache.put(key.to_string(), value.to_string());
+    }
+
+    async fn invalidate(&self, key: &str) {
+        let mut cache = self.inner.lock().unwrap();
+        cache.pop(key);
+    }
+}
+
+/// Build the configured cache from the merged [`CacheConfig`](crate::config::CacheConfig):
+/// Redis when a URL is set, otherwise an in-memory LRU fallback.
+pub fn from_config(cfg: &crate::config::CacheConfig) -> std::sync::Arc<dyn ResultCache> {
+    if !cfg.redis_url.is_empty() {
+        match RedisCache::new(&cfg.redis_url, cfg.ttl_secs) {
+            Ok(cache) => return std::sync::Arc::new(cache),
+            Err(e) => tracing::warn!("Redis cache unavailable, falling back to in-memory: {}", e),
+        }
+    }
+    std::sync::Arc::new(InMemoryCache::new(cfg.lru_capacity))
+}
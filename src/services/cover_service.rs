@@ -0,0 +1,63 @@
+use anyhow::Result;
+use mongodb::bson::{doc, oid::ObjectId, spec::BinarySubtype, Binary, Document};
+
+use crate::db::DatabaseConnection;
+
+/// A stored cover image: its normalized bytes and the MIME type to serve it
+/// back with.
+pub struct StoredCover {
+    pub content_type: String,
+    pub data: Vec<u8>,
+}
+
+/// Persists normalized cover images in the `covers` collection, keyed by the
+/// owning novel's `_id` so each novel has at most one cover and lookups are a
+/// primary-key hit. Kept out of the `novels` documents so list queries don't
+/// drag binary blobs into memory.
+pub struct CoverService {
+    db: DatabaseConnection,
+}
+
+impl CoverService {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Upsert the cover for `novel_id`, replacing any existing image.
+    pub async fn store(&self, novel_id: &ObjectId, content_type: &str, data: Vec<u8>) -> Result<()> {
+        let collection = self.db.get_collection::<Document>("covers");
+        let binary = Binary {
+            subtype: BinarySubtype::Generic,
+            bytes: data,
+        };
+        let update = doc! {
+            "$set": {
+                "content_type": content_type,
+                "data": binary,
+            }
+        };
+        collection
+            .update_one(
+                doc! { "_id": novel_id },
+                update,
+                mongodb::options::UpdateOptions::builder().upsert(true).build(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Fetch the stored cover for `novel_id`, if one has been uploaded.
+    pub async fn fetch(&self, novel_id: &ObjectId) -> Result<Option<StoredCover>> {
+        let collection = self.db.get_collection::<Document>("covers");
+        let doc = match collection.find_one(doc! { "_id": novel_id }, None).await? {
+            Some(doc) => doc,
+            None => return Ok(None),
+        };
+        let content_type = doc.get_str("content_type").unwrap_or("application/octet-stream").to_string();
+        let data = doc
+            .get_binary_generic("data")
+            .map(|bytes| bytes.to_vec())
+            .unwrap_or_default();
+        Ok(Some(StoredCover { content_type, data }))
+    }
+}
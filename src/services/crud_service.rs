@@ -8,26 +8,111 @@ use futures::TryStreamExt; // Add TryStreamExt trait
 
 use crate::db::DatabaseConnection;
 use crate::models::{Novel, Chapter, Character, QA};
+use crate::services::pagination::{self, ListOptions, Order, Page, SortField};
 
 /// CRUD operations trait for MongoDB collections
 #[async_trait]
 pub trait CrudService<T> {
+    /// The model's type-safe sortable fields.
+    type SortKey: SortField;
+
     /// Create a new document in the collection
     async fn create(&self, item: &T) -> Result<ObjectId>;
-    
+
     /// Read a document by its ID
     async fn read_by_id(&self, id: &ObjectId) -> Result<Option<T>>;
-    
+
     /// Read multiple documents matching a filter
     async fn read_many(&self, filter: Document, limit: Option<i64>) -> Result<Vec<T>>;
-    
+
+    /// Read one keyset page matching `filter`, ordered by `opts.sort`, starting
+    /// after `opts.after`. Returns the page plus a cursor for the next page.
+    async fn read_page(&self, filter: Document, opts: ListOptions<Self::SortKey>) -> Result<Page<T>>;
+
     /// Update a document by its ID
     async fn update(&self, id: &ObjectId, update: Document) -> Result<Option<T>>;
-    
+
     /// Delete a document by its ID
     async fn delete(&self, id: &ObjectId) -> Result<bool>;
 }
 
+/// Sortable fields on the `novels` collection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NovelSortKey {
+    Title,
+    Author,
+}
+
+impl SortField for NovelSortKey {
+    fn field(&self) -> &'static str {
+        match self {
+            NovelSortKey::Title => "title",
+            NovelSortKey::Author => "author",
+        }
+    }
+}
+
+/// Sortable fields on the `chapters` collection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChapterSortKey {
+    ChapterNumber,
+    Title,
+}
+
+impl SortField for ChapterSortKey {
+    fn field(&self) -> &'static str {
+        match self {
+            ChapterSortKey::ChapterNumber => "number",
+            ChapterSortKey::Title => "title",
+        }
+    }
+}
+
+/// Sortable fields on the `characters` collection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharacterSortKey {
+    Name,
+    Role,
+}
+
+impl SortField for CharacterSortKey {
+    fn field(&self) -> &'static str {
+        match self {
+            CharacterSortKey::Name => "name",
+            CharacterSortKey::Role => "role",
+        }
+    }
+}
+
+/// Sortable fields on the `qa` collection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QASortKey {
+    Question,
+}
+
+impl SortField for QASortKey {
+    fn field(&self) -> &'static str {
+        match self {
+            QASortKey::Question => "question",
+        }
+    }
+}
+
+/// Look up a novel's `owner_id` for ownership checks. Returns `None` when the
+/// novel does not exist, and `Some(None)` when it exists but has no recorded
+/// owner (e.g. created before authentication was enforced).
+pub async fn novel_owner(db: &DatabaseConnection, novel_id: &ObjectId) -> Result<Option<Option<String>>> {
+    let collection = db.get_collection::<Novel>("novels");
+    let novel = collection.find_one(doc! { "_id": novel_id }, None).await?;
+    Ok(novel.map(|n| n.owner_id))
+}
+
+/// Map a model's `ListOptions` sort list to `(field, order)` pairs for the
+/// shared [`pagination::fetch_page`] helper.
+fn sort_fields<K: SortField>(sort: &[(K, Order)]) -> Vec<(&'static str, Order)> {
+    sort.iter().map(|(key, order)| (key.field(), *order)).collect()
+}
+
 /// MongoDB CRUD implementation for Novel collection
 pub struct NovelCrudService {
     db: DatabaseConnection,
@@ -64,6 +149,26 @@ impl NovelCrudService {
         Ok(novels)
     }
     
+    /// Delete a novel and cascade to its chapters and characters so orphaned
+    /// `novel_id` references don't accumulate. Returns whether the novel itself
+    /// existed; the child deletions run either way, and an error from them is
+    /// returned even though the novel itself is already deleted.
+    pub async fn delete_cascade(&self, id: &ObjectId) -> Result<bool> {
+        let existed = self.delete(id).await?;
+
+        let child_filter = doc! { "novel_id": id };
+        self.db
+            .get_collection::<Chapter>("chapters")
+            .delete_many(child_filter.clone(), None)
+            .await?;
+        self.db
+            .get_collection::<Character>("characters")
+            .delete_many(child_filter, None)
+            .await?;
+
+        Ok(existed)
+    }
+
     /// Find novels by tags (any match)
     pub async fn find_by_tags(&self, tags: &[String]) -> Result<Vec<Novel>> {
         let collection = self.db.get_collection::<Novel>("novels");
@@ -80,6 +185,12 @@ impl NovelCrudService {
 
 #[async_trait]
 impl CrudService<Novel> for NovelCrudService {
+    type SortKey = NovelSortKey;
+
+    async fn read_page(&self, filter: Document, opts: ListOptions<NovelSortKey>) -> Result<Page<Novel>> {
+        pagination::fetch_page(&self.db, "novels", filter, &sort_fields(&opts.sort), opts.limit, opts.after.as_ref()).await
+    }
+
     async fn create(&self, novel: &Novel) -> Result<ObjectId> {
         let collection = self.db.get_collection::<Novel>("novels");
         
@@ -164,6 +275,12 @@ impl ChapterCrudService {
         Ok(chapters)
     }
     
+    /// Look up the owner of the chapter's parent novel for authorization.
+    /// See [`novel_owner`] for the meaning of the nested `Option`.
+    pub async fn parent_novel_owner(&self, novel_id: &ObjectId) -> Result<Option<Option<String>>> {
+        novel_owner(&self.db, novel_id).await
+    }
+
     /// Find a specific chapter number in a novel
     pub async fn find_by_novel_and_number(&self, novel_id: &ObjectId, chapter_number: u32) -> Result<Option<Chapter>> {
         let collection = self.db.get_collection::<Chapter>("chapters");
@@ -199,6 +316,12 @@ impl ChapterCrudService {
 
 #[async_trait]
 impl CrudService<Chapter> for ChapterCrudService {
+    type SortKey = ChapterSortKey;
+
+    async fn read_page(&self, filter: Document, opts: ListOptions<ChapterSortKey>) -> Result<Page<Chapter>> {
+        pagination::fetch_page(&self.db, "chapters", filter, &sort_fields(&opts.sort), opts.limit, opts.after.as_ref()).await
+    }
+
     async fn create(&self, chapter: &Chapter) -> Result<ObjectId> {
         let collection = self.db.get_collection::<Chapter>("chapters");
         
@@ -283,6 +406,12 @@ impl CharacterCrudService {
         Ok(characters)
     }
     
+    /// Look up the owner of the character's parent novel for authorization.
+    /// See [`novel_owner`] for the meaning of the nested `Option`.
+    pub async fn parent_novel_owner(&self, novel_id: &ObjectId) -> Result<Option<Option<String>>> {
+        novel_owner(&self.db, novel_id).await
+    }
+
     /// Find character by name in a specific novel (case-insensitive)
     pub async fn find_by_novel_and_name(&self, novel_id: &ObjectId, name: &str) -> Result<Option<Character>> {
         let collection = self.db.get_collection::<Character>("characters");
@@ -313,6 +442,12 @@ impl CharacterCrudService {
 
 #[async_trait]
 impl CrudService<Character> for CharacterCrudService {
+    type SortKey = CharacterSortKey;
+
+    async fn read_page(&self, filter: Document, opts: ListOptions<CharacterSortKey>) -> Result<Page<Character>> {
+        pagination::fetch_page(&self.db, "characters", filter, &sort_fields(&opts.sort), opts.limit, opts.after.as_ref()).await
+    }
+
     async fn create(&self, character: &Character) -> Result<ObjectId> {
         let collection = self.db.get_collection::<Character>("characters");
         
@@ -428,6 +563,12 @@ impl QACrudService {
 
 #[async_trait]
 impl CrudService<QA> for QACrudService {
+    type SortKey = QASortKey;
+
+    async fn read_page(&self, filter: Document, opts: ListOptions<QASortKey>) -> Result<Page<QA>> {
+        pagination::fetch_page(&self.db, "qa", filter, &sort_fields(&opts.sort), opts.limit, opts.after.as_ref()).await
+    }
+
     async fn create(&self, qa: &QA) -> Result<ObjectId> {
         let collection = self.db.get_collection::<QA>("qa");
         
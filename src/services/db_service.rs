@@ -2,14 +2,20 @@ use anyhow::Result;
 use std::sync::Arc;
 use async_trait::async_trait;
 use mongodb::{
-    bson::{doc, Document, oid::ObjectId},
-    options::FindOptions,
+    bson::{doc, Bson, Document, oid::ObjectId},
+    options::{FindOneOptions, FindOptions},
 };
 use std::time::{Duration, Instant};
 use futures::TryStreamExt; // Add the TryStreamExt trait
 
 use crate::db::DatabaseConnection;
-use crate::models::{Chapter, Character, MCPResponse, Novel, QA, ResponseMetadata, SearchParams};
+use crate::models::{Chapter, Character, MCPResponse, McpError, Novel, QA, ResponseMetadata, SearchParams};
+use crate::services::filter_dsl;
+use crate::services::page_token::PageToken;
+use crate::utils::query_tree::{allowed_edits, LevenshteinNfa};
+
+/// Collections reported by [`DatabaseService::database_stats`].
+const STAT_COLLECTIONS: [&str; 4] = ["novels", "chapters", "characters", "qa"];
 
 #[async_trait]
 pub trait DatabaseService {
@@ -24,6 +30,79 @@ pub trait DatabaseService {
     async fn get_chapter_content(&self, chapter_id: &str) -> Result<Option<String>>;
     async fn get_character_details(&self, character_id: &str) -> Result<Option<Character>>;
     async fn update_chapter_summary(&self, chapter_id: &str, new_summary: &str) -> Result<()>;
+
+    /// Run an Atlas `$vectorSearch` over `collection`'s stored `embedding`
+    /// field against `query_embedding`, returning the nearest documents as an
+    /// [`MCPResponse`] carrying their estimated token count.
+    async fn semantic_search(
+        &self,
+        collection: mongodb::Collection<Document>,
+        query_embedding: &[f32],
+        params: &SearchParams,
+    ) -> Result<MCPResponse>;
+
+    /// Given a chapter id, surface the nearest chapters by embedding similarity,
+    /// excluding the source chapter. Each result carries its similarity score.
+    async fn get_similar_chapters(&self, chapter_id: &str, limit: u32) -> Result<MCPResponse>;
+
+    /// Given a character id, surface the nearest characters by embedding
+    /// similarity, excluding the source character.
+    async fn get_similar_characters(&self, character_id: &str, limit: u32) -> Result<MCPResponse>;
+
+    /// Run the multi-collection search and greedily pack the most relevant
+    /// results into a `max_tokens` budget, returning a ready-to-prompt context
+    /// block plus a `used_tokens`/`dropped` summary.
+    async fn assemble_context(
+        &self,
+        params: &SearchParams,
+        max_tokens: u32,
+    ) -> Result<serde_json::Value>;
+
+    /// Typo-tolerant ranked search over novel titles/summaries, character
+    /// names/descriptions, and chapter titles/summaries/content. Returns the
+    /// hits grouped into `novels`/`chapters`/`characters` arrays, each already
+    /// ordered by the composite ranking so the `format_*` helpers can render
+    /// them directly. `max_edits` caps the per-token edit budget (below the
+    /// length-scaled default) for tighter matching in small context windows.
+    async fn fuzzy_search(
+        &self,
+        query: &str,
+        max_edits: Option<usize>,
+        limit: usize,
+    ) -> Result<serde_json::Value>;
+
+    /// Record that `term` and `alias` are interchangeable, persisting the pair
+    /// to the `synonyms` collection (idempotent on the unordered pair).
+    async fn add_synonym(&self, term: &str, alias: &str) -> Result<()>;
+
+    /// Drop a synonym pair in either direction. Returns whether a pair matched.
+    async fn remove_synonym(&self, term: &str, alias: &str) -> Result<bool>;
+
+    /// Every persisted synonym pair, as `(term, alias)` tuples.
+    async fn list_synonyms(&self) -> Result<Vec<(String, String)>>;
+
+    /// Load the full synonym set into an in-memory [`Synonyms`] map for the
+    /// handler to cache and consult on each query.
+    async fn load_synonyms(&self) -> Result<crate::services::synonyms::Synonyms>;
+
+    /// Dataset size and freshness snapshot: per-collection document counts and
+    /// storage size (via `collStats`), total/average chapter content length,
+    /// the newest document timestamp per collection (derived from the `_id`),
+    /// and the database-wide `dbStats` totals.
+    async fn database_stats(&self) -> Result<serde_json::Value>;
+}
+
+/// The Atlas vector index name queried by [`DatabaseService::semantic_search`].
+const VECTOR_INDEX_NAME: &str = "embedding_index";
+
+/// Convert an `f32` query vector into a BSON array of doubles for `$vectorSearch`.
+fn embedding_to_bson(embedding: &[f32]) -> mongodb::bson::Bson {
+    mongodb::bson::Bson::Array(
+        embedding
+            .iter()
+            .map(|value| mongodb::bson::Bson::Double(*value as f64))
+            .collect(),
+    )
 }
 
 #[derive(Clone)]
@@ -32,11 +111,80 @@ pub struct MongoDBService {
 }
 
 impl MongoDBService {
-    pub async fn new() -> Result<Self> {
-        let db = DatabaseConnection::new().await?;
+    pub async fn new(mongo: &crate::config::MongoConfig) -> Result<Self> {
+        let db = DatabaseConnection::new(&mongo.uri, &mongo.database, mongo.pool_max_size).await?;
         Ok(Self { db })
     }
 
+    /// Borrow a clone of the underlying pooled [`DatabaseConnection`], used by
+    /// CRUD services and the management CLI.
+    pub fn db_connection(&self) -> DatabaseConnection {
+        self.db.clone()
+    }
+
+    /// Run a nearest-neighbor query over `collection_name` seeded by the stored
+    /// embedding of `source_id`, excluding the source document itself. Each
+    /// result carries a `similarity_score` from the vector index.
+    async fn find_similar(
+        &self,
+        collection_name: &str,
+        source_id: &str,
+        limit: u32,
+    ) -> Result<MCPResponse> {
+        let start = Instant::now();
+        let object_id = ObjectId::parse_str(source_id)?;
+
+        let collection = self.db.get_collection::<Document>(collection_name);
+
+        // Pull just the source document's embedding to seed the search.
+        let options = FindOneOptions::builder()
+            .projection(doc! { "embedding": 1 })
+            .build();
+        let source = collection
+            .find_one(doc! { "_id": object_id }, options)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Source document not found"))?;
+        let query_embedding: Vec<f32> = source
+            .get_array("embedding")
+            .map_err(|_| anyhow::anyhow!("Source document has no embedding"))?
+            .iter()
+            .filter_map(|value| value.as_f64().map(|f| f as f32))
+            .collect();
+
+        let limit = limit as i64;
+        let num_candidates = (limit * 10).max(100);
+
+        let pipeline = vec![
+            doc! {
+                "$vectorSearch": {
+                    "index": VECTOR_INDEX_NAME,
+                    "path": "embedding",
+                    "queryVector": embedding_to_bson(&query_embedding),
+                    "numCandidates": num_candidates,
+                    "limit": limit + 1, // One extra to detect has_more
+                    "filter": { "_id": { "$ne": object_id } }, // Exclude the source
+                }
+            },
+            // Expose the similarity so callers can threshold relatives.
+            doc! { "$set": { "similarity_score": { "$meta": "vectorSearchScore" } } },
+            doc! { "$project": { "embedding": 0 } },
+        ];
+
+        let mut cursor = collection.aggregate(pipeline, None).await?;
+        let mut docs: Vec<Document> = Vec::new();
+        while let Some(doc) = cursor.try_next().await? {
+            docs.push(doc);
+        }
+
+        let has_more = docs.len() > limit as usize;
+        if has_more {
+            docs.pop();
+        }
+
+        let query_time = start.elapsed();
+        Ok(self.format_response(docs, query_time, has_more, None).await)
+    }
+
     // Helper function to estimate token count of JSON data
     fn estimate_token_count(data: &serde_json::Value) -> u32 {
         // Very rough estimate: 1 token â‰ˆ 4 chars in English text
@@ -44,32 +192,22 @@ impl MongoDBService {
         (json_string.len() as u32 + 3) / 4
     }
 
-    // Helper to convert MongoDB documents to JSON
-    async fn format_response<T>(&self, 
-        data: Vec<T>, 
-        query_time: Duration, 
+    // Helper to convert MongoDB documents to JSON. The caller supplies the
+    // keyset `next_page_token` (see `page_token`) since it owns the sort key.
+    async fn format_response<T>(&self,
+        data: Vec<T>,
+        query_time: Duration,
         has_more: bool,
-        _limit: Option<u32>
-    ) -> MCPResponse 
-    where 
-        T: serde::Serialize 
+        next_page_token: Option<String>,
+    ) -> MCPResponse
+    where
+        T: serde::Serialize
     {
         let data_json = serde_json::to_value(data).unwrap_or(serde_json::Value::Array(vec![]));
-        
+
         // Estimate token count
         let token_count = Self::estimate_token_count(&data_json);
-        
-        // Create next page token if there are more results
-        let next_page_token = if has_more {
-            // In a real implementation, we would create a proper pagination token
-            Some(format!("page_token_{}", std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs()))
-        } else {
-            None
-        };
-        
+
         MCPResponse {
             status: "success".to_string(),
             data: data_json,
@@ -78,11 +216,36 @@ impl MongoDBService {
                 query_time_ms: query_time.as_millis() as u64,
                 has_more,
                 next_page_token,
+                content_encoding: None,
+                uncompressed_bytes: None,
+                compressed_bytes: None,
             },
         }
     }
 }
 
+/// Primary text field whose distinct indexed terms seed the boolean query
+/// tree's typo-tolerant expansion, per collection.
+fn tree_field(collection: &str) -> Option<&'static str> {
+    match collection {
+        "novels" | "chapters" => Some("title"),
+        "characters" => Some("name"),
+        "qa" => Some("question"),
+        _ => None,
+    }
+}
+
+/// Whether a raw query uses explicit boolean search syntax — the `AND`/`OR`
+/// keywords, parentheses, or a quoted phrase — in which case it is routed
+/// through the typo-tolerant [`query_tree`](crate::utils::query_tree) rather
+/// than the flat `$text` keyword bag.
+fn has_boolean_syntax(query: &str) -> bool {
+    query.contains('(') || query.contains('"')
+        || query
+            .split_whitespace()
+            .any(|word| word == "AND" || word == "OR")
+}
+
 // Helper function to build a search filter based on keywords
 fn build_text_search_filter(keywords: &[String]) -> Document {
     if keywords.is_empty() {
@@ -99,6 +262,60 @@ fn build_text_search_filter(keywords: &[String]) -> Document {
     }
 }
 
+// Translate the richer structured filter fields (set membership, numeric and
+// date ranges, tag negation) onto an existing filter document.
+fn apply_structured_filters(mut filter: Document, filters: &crate::models::SearchFilters) -> Document {
+    if let Some(ids) = &filters.ids {
+        let oids: Vec<Bson> = ids
+            .iter()
+            .filter_map(|id| ObjectId::parse_str(id).ok().map(Bson::ObjectId))
+            .collect();
+        if !oids.is_empty() {
+            filter.insert("_id", doc! { "$in": oids });
+        }
+    }
+
+    if let Some(not_tags) = &filters.not_tags {
+        filter.insert("tags", doc! { "$nin": not_tags });
+    }
+
+    let mut number_range = Document::new();
+    if let Some(gte) = filters.number_gte {
+        number_range.insert("$gte", gte);
+    }
+    if let Some(lte) = filters.number_lte {
+        number_range.insert("$lte", lte);
+    }
+    if !number_range.is_empty() {
+        filter.insert("number", number_range);
+    }
+
+    let mut date_range = Document::new();
+    if let Some(since) = &filters.since {
+        date_range.insert("$gte", since);
+    }
+    if let Some(until) = &filters.until {
+        date_range.insert("$lte", until);
+    }
+    if !date_range.is_empty() {
+        filter.insert("created_at", date_range);
+    }
+
+    filter
+}
+
+// Combine the text/attribute filter with a keyset range predicate without
+// either clobbering the other's keys.
+fn merge_filter(base: Document, extra: Document) -> Document {
+    if base.is_empty() {
+        extra
+    } else if extra.is_empty() {
+        base
+    } else {
+        doc! { "$and": [Bson::Document(base), Bson::Document(extra)] }
+    }
+}
+
 #[async_trait]
 impl DatabaseService for Arc<MongoDBService> {
     async fn search_novels(&self, params: &SearchParams) -> Result<MCPResponse> {
@@ -112,34 +329,67 @@ impl DatabaseService for Arc<MongoDBService> {
             if let Some(tags) = &filters.tags {
                 filter.insert("tags", doc! { "$in": tags });
             }
+            filter = apply_structured_filters(filter, filters);
         }
-        
+
+        // Merge any compact filter expression.
+        if let Some(expr) = &params.filter_expr {
+            filter = merge_filter(filter, filter_dsl::parse_filter(expr)?);
+        }
+
+        // Merge the predicate filter compiled from the query's comparison clauses.
+        if let Some(compiled) = &params.compiled_filter {
+            filter = merge_filter(filter, compiled.clone());
+        }
+
+        // Merge the typo-tolerant boolean query tree when the query uses
+        // AND/OR, parentheses, or quoted phrases.
+        if let Some(tree) = self.boolean_tree_filter("novels", params.text_query.as_deref()).await? {
+            filter = merge_filter(filter, tree);
+        }
+
         // Set limit for small context window optimization
         let limit = params.limit.unwrap_or(5);
+
+        // Apply an incoming keyset cursor, anchored on `_id`.
+        if let Some(token) = &params.page_token {
+            let decoded = PageToken::decode(token, "novels", limit)?;
+            filter = merge_filter(filter, decoded.predicate());
+        }
+
         let options = FindOptions::builder()
             .limit(limit as i64 + 1) // Fetch one extra to check if there are more
+            .sort(doc! { "_id": 1 })
             .build();
-        
+
         // Execute query
         let collection = self.db.get_collection::<Novel>("novels");
         let mut cursor = collection.find(filter, options).await?;
-        
+
         // Collect results
         let mut novels = Vec::new();
         while let Some(novel) = cursor.try_next().await? {
             novels.push(novel);
         }
-        
+
         // Check if there are more results
         let has_more = novels.len() > limit as usize;
         if has_more {
             novels.pop(); // Remove the extra item
         }
-        
+
+        let next_page_token = has_more
+            .then(|| novels.last())
+            .flatten()
+            .and_then(|novel| novel.id)
+            .map(|id| PageToken::new("novels", limit, "_id", Bson::ObjectId(id)).encode());
+
         let query_time = start.elapsed();
-        Ok(self.format_response(novels, query_time, has_more, Some(limit)).await)
+        let mut response = self.format_response(novels, query_time, has_more, next_page_token).await;
+        apply_fuzzy_ranking(&mut response.data, NOVEL_FIELDS, &params.keywords);
+        Ok(response)
     }
-    
+
     async fn search_chapters(&self, params: &SearchParams) -> Result<MCPResponse> {
         let start = Instant::now();
         
@@ -154,15 +404,39 @@ impl DatabaseService for Arc<MongoDBService> {
                     filter.insert("novel_id", oid);
                 }
             }
+            filter = apply_structured_filters(filter, filters);
         }
-        
+
+        // Merge any compact filter expression.
+        if let Some(expr) = &params.filter_expr {
+            filter = merge_filter(filter, filter_dsl::parse_filter(expr)?);
+        }
+
+        // Merge the predicate filter compiled from the query's comparison clauses.
+        if let Some(compiled) = &params.compiled_filter {
+            filter = merge_filter(filter, compiled.clone());
+        }
+
+        // Merge the typo-tolerant boolean query tree when the query uses
+        // AND/OR, parentheses, or quoted phrases.
+        if let Some(tree) = self.boolean_tree_filter("chapters", params.text_query.as_deref()).await? {
+            filter = merge_filter(filter, tree);
+        }
+
         // Set limit for small context window optimization
         let limit = params.limit.unwrap_or(3);
+
+        // Apply an incoming keyset cursor, anchored on chapter `number`.
+        if let Some(token) = &params.page_token {
+            let decoded = PageToken::decode(token, "chapters", limit)?;
+            filter = merge_filter(filter, decoded.predicate());
+        }
+
         let options = FindOptions::builder()
             .limit(limit as i64 + 1) // Fetch one extra to check if there are more
             .sort(doc! { "number": 1 }) // Sort by chapter number
             .build();
-        
+
         // Execute query
         let collection = self.db.get_collection::<Chapter>("chapters");
         let mut cursor = collection.find(filter, options).await?;
@@ -179,6 +453,7 @@ impl DatabaseService for Arc<MongoDBService> {
                 summary: chapter.summary,
                 key_points: chapter.key_points,
                 content: None, // Exclude full content to save tokens
+                embedding: None, // Never surfaced to the LLM
             };
             chapters.push(compact_chapter);
         }
@@ -188,11 +463,21 @@ impl DatabaseService for Arc<MongoDBService> {
         if has_more {
             chapters.pop(); // Remove the extra item
         }
-        
+
+        let next_page_token = has_more
+            .then(|| chapters.last())
+            .flatten()
+            .map(|chapter| {
+                PageToken::new("chapters", limit, "number", Bson::Int64(chapter.number as i64))
+                    .encode()
+            });
+
         let query_time = start.elapsed();
-        Ok(self.format_response(chapters, query_time, has_more, Some(limit)).await)
+        let mut response = self.format_response(chapters, query_time, has_more, next_page_token).await;
+        apply_fuzzy_ranking(&mut response.data, CHAPTER_FIELDS, &params.keywords);
+        Ok(response)
     }
-    
+
     async fn search_characters(&self, params: &SearchParams) -> Result<MCPResponse> {
         let start = Instant::now();
         
@@ -211,35 +496,69 @@ impl DatabaseService for Arc<MongoDBService> {
             if let Some(character_name) = &filters.character_name {
                 filter.insert("name", doc! { "$regex": character_name, "$options": "i" });
             }
+            filter = apply_structured_filters(filter, filters);
         }
-        
+
+        // Merge any compact filter expression.
+        if let Some(expr) = &params.filter_expr {
+            filter = merge_filter(filter, filter_dsl::parse_filter(expr)?);
+        }
+
+        // Merge the predicate filter compiled from the query's comparison clauses.
+        if let Some(compiled) = &params.compiled_filter {
+            filter = merge_filter(filter, compiled.clone());
+        }
+
+        // Merge the typo-tolerant boolean query tree when the query uses
+        // AND/OR, parentheses, or quoted phrases.
+        if let Some(tree) = self.boolean_tree_filter("characters", params.text_query.as_deref()).await? {
+            filter = merge_filter(filter, tree);
+        }
+
         // Set limit for small context window optimization
         let limit = params.limit.unwrap_or(5);
+
+        // Apply an incoming keyset cursor, anchored on character `name`.
+        if let Some(token) = &params.page_token {
+            let decoded = PageToken::decode(token, "characters", limit)?;
+            filter = merge_filter(filter, decoded.predicate());
+        }
+
         let options = FindOptions::builder()
             .limit(limit as i64 + 1) // Fetch one extra to check if there are more
             .sort(doc! { "name": 1 }) // Sort by character name
             .build();
-        
+
         // Execute query
         let collection = self.db.get_collection::<Character>("characters");
         let mut cursor = collection.find(filter, options).await?;
-        
+
         // Collect results
         let mut characters = Vec::new();
         while let Some(character) = cursor.try_next().await? {
             characters.push(character);
         }
-        
+
         // Check if there are more results
         let has_more = characters.len() > limit as usize;
         if has_more {
             characters.pop(); // Remove the extra item
         }
-        
+
+        let next_page_token = has_more
+            .then(|| characters.last())
+            .flatten()
+            .map(|character| {
+                PageToken::new("characters", limit, "name", Bson::String(character.name.clone()))
+                    .encode()
+            });
+
         let query_time = start.elapsed();
-        Ok(self.format_response(characters, query_time, has_more, Some(limit)).await)
+        let mut response = self.format_response(characters, query_time, has_more, next_page_token).await;
+        apply_fuzzy_ranking(&mut response.data, CHARACTER_FIELDS, &params.keywords);
+        Ok(response)
     }
-    
+
     async fn search_qa(&self, params: &SearchParams) -> Result<MCPResponse> {
         let start = Instant::now();
         
@@ -258,32 +577,63 @@ impl DatabaseService for Arc<MongoDBService> {
             if let Some(tags) = &filters.tags {
                 filter.insert("tags", doc! { "$in": tags });
             }
+            filter = apply_structured_filters(filter, filters);
         }
-        
+
+        // Merge any compact filter expression.
+        if let Some(expr) = &params.filter_expr {
+            filter = merge_filter(filter, filter_dsl::parse_filter(expr)?);
+        }
+
+        // Merge the predicate filter compiled from the query's comparison clauses.
+        if let Some(compiled) = &params.compiled_filter {
+            filter = merge_filter(filter, compiled.clone());
+        }
+
+        // Merge the typo-tolerant boolean query tree when the query uses
+        // AND/OR, parentheses, or quoted phrases.
+        if let Some(tree) = self.boolean_tree_filter("qa", params.text_query.as_deref()).await? {
+            filter = merge_filter(filter, tree);
+        }
+
         // Set limit for small context window optimization
         let limit = params.limit.unwrap_or(3);
+
+        // Apply an incoming keyset cursor, anchored on `_id`.
+        if let Some(token) = &params.page_token {
+            let decoded = PageToken::decode(token, "qa", limit)?;
+            filter = merge_filter(filter, decoded.predicate());
+        }
+
         let options = FindOptions::builder()
             .limit(limit as i64 + 1) // Fetch one extra to check if there are more
+            .sort(doc! { "_id": 1 })
             .build();
-        
+
         // Execute query
         let collection = self.db.get_collection::<QA>("qa");
         let mut cursor = collection.find(filter, options).await?;
-        
+
         // Collect results
         let mut qa_entries = Vec::new();
         while let Some(qa) = cursor.try_next().await? {
             qa_entries.push(qa);
         }
-        
+
         // Check if there are more results
         let has_more = qa_entries.len() > limit as usize;
         if has_more {
             qa_entries.pop(); // Remove the extra item
         }
-        
+
+        let next_page_token = has_more
+            .then(|| qa_entries.last())
+            .flatten()
+            .and_then(|qa| qa.id)
+            .map(|id| PageToken::new("qa", limit, "_id", Bson::ObjectId(id)).encode());
+
         let query_time = start.elapsed();
-        Ok(self.format_response(qa_entries, query_time, has_more, Some(limit)).await)
+        Ok(self.format_response(qa_entries, query_time, has_more, next_page_token).await)
     }
 
     async fn search_qa_by_regex(&self, regex_pattern: &str) -> Result<Vec<serde_json::Value>> {
@@ -295,9 +645,10 @@ impl DatabaseService for Arc<MongoDBService> {
             ]
         };
         
-        // Execute query
+        // Execute query with a stable sort so cursor windowing is deterministic.
+        let options = FindOptions::builder().sort(doc! { "_id": 1 }).build();
         let collection = self.db.get_collection::<QA>("qa");
-        let cursor = collection.find(filter, None).await?;
+        let cursor = collection.find(filter, options).await?;
         let qa_entries: Vec<QA> = cursor.try_collect().await?;
         
         // Convert to serde_json::Value
@@ -320,8 +671,10 @@ impl DatabaseService for Arc<MongoDBService> {
         };
         
         // Execute query with projection to exclude content for token efficiency
+        // and a stable sort so cursor windowing is deterministic.
         let options = FindOptions::builder()
             .projection(doc! { "content": 0 })
+            .sort(doc! { "_id": 1 })
             .build();
             
         let collection = self.db.get_collection::<Chapter>("chapters");
@@ -347,9 +700,10 @@ impl DatabaseService for Arc<MongoDBService> {
             ]
         };
         
-        // Execute query
+        // Execute query with a stable sort so cursor windowing is deterministic.
+        let options = FindOptions::builder().sort(doc! { "_id": 1 }).build();
         let collection = self.db.get_collection::<Character>("characters");
-        let cursor = collection.find(filter, None).await?;
+        let cursor = collection.find(filter, options).await?;
         let characters: Vec<Character> = cursor.try_collect().await?;
         
         // Convert to serde_json::Value
@@ -363,7 +717,8 @@ impl DatabaseService for Arc<MongoDBService> {
     
     async fn update_chapter_summary(&self, chapter_id: &str, new_summary: &str) -> Result<()> {
         // Convert string ID to ObjectId
-        let object_id = ObjectId::parse_str(chapter_id)?;
+        let object_id = ObjectId::parse_str(chapter_id)
+            .map_err(|_| McpError::InvalidId(chapter_id.to_string()))?;
         
         // Create update document
         let update = doc! {
@@ -377,9 +732,9 @@ impl DatabaseService for Arc<MongoDBService> {
         let result = collection.update_one(doc! { "_id": object_id }, update, None).await?;
         
         if result.matched_count == 0 {
-            return Err(anyhow::anyhow!("Chapter not found"));
+            return Err(McpError::NotFound("Chapter".to_string()).into());
         }
-        
+
         Ok(())
     }
 
@@ -417,11 +772,9 @@ impl DatabaseService for Arc<MongoDBService> {
 
     async fn get_chapter_content(&self, chapter_id: &str) -> Result<Option<String>> {
         // Convert string ID to ObjectId
-        let object_id = match ObjectId::parse_str(chapter_id) {
-            Ok(oid) => oid,
-            Err(_) => return Ok(None), // Invalid ID format, return None
-        };
-        
+        let object_id = ObjectId::parse_str(chapter_id)
+            .map_err(|_| McpError::InvalidId(chapter_id.to_string()))?;
+
         // Query for the chapter
         let filter = doc! { "_id": object_id };
         let collection = self.db.get_collection::<Chapter>("chapters");
@@ -442,17 +795,603 @@ impl DatabaseService for Arc<MongoDBService> {
 
     async fn get_character_details(&self, character_id: &str) -> Result<Option<Character>> {
         // Convert string ID to ObjectId
-        let object_id = match ObjectId::parse_str(character_id) {
-            Ok(oid) => oid,
-            Err(_) => return Ok(None), // Invalid ID format, return None
-        };
-        
+        let object_id = ObjectId::parse_str(character_id)
+            .map_err(|_| McpError::InvalidId(character_id.to_string()))?;
+
         // Query for the character
         let filter = doc! { "_id": object_id };
         let collection = self.db.get_collection::<Character>("characters");
-        
+
         // Return the character if found
         let character = collection.find_one(filter, None).await?;
         Ok(character)
     }
+
+    async fn semantic_search(
+        &self,
+        collection: mongodb::Collection<Document>,
+        query_embedding: &[f32],
+        params: &SearchParams,
+    ) -> Result<MCPResponse> {
+        let start = Instant::now();
+
+        let limit = params.limit.unwrap_or(5) as i64;
+        // Atlas recommends over-fetching candidates relative to the final limit.
+        let num_candidates = (limit * 10).max(100);
+
+        let pipeline = vec![
+            doc! {
+                "$vectorSearch": {
+                    "index": VECTOR_INDEX_NAME,
+                    "path": "embedding",
+                    "queryVector": embedding_to_bson(query_embedding),
+                    "numCandidates": num_candidates,
+                    "limit": limit + 1, // One extra to detect has_more
+                }
+            },
+            // Never surface the raw vector to the caller.
+            doc! { "$project": { "embedding": 0 } },
+        ];
+
+        let mut cursor = collection.aggregate(pipeline, None).await?;
+        let mut docs: Vec<Document> = Vec::new();
+        while let Some(doc) = cursor.try_next().await? {
+            docs.push(doc);
+        }
+
+        let has_more = docs.len() > limit as usize;
+        if has_more {
+            docs.pop();
+        }
+
+        let query_time = start.elapsed();
+        Ok(self.format_response(docs, query_time, has_more, None).await)
+    }
+
+    async fn get_similar_chapters(&self, chapter_id: &str, limit: u32) -> Result<MCPResponse> {
+        self.find_similar("chapters", chapter_id, limit).await
+    }
+
+    async fn get_similar_characters(&self, character_id: &str, limit: u32) -> Result<MCPResponse> {
+        self.find_similar("characters", character_id, limit).await
+    }
+
+    async fn assemble_context(
+        &self,
+        params: &SearchParams,
+        max_tokens: u32,
+    ) -> Result<serde_json::Value> {
+        // Reuse the parallel multi-collection search, which already strips
+        // chapter content down to summaries and key points.
+        let combined = self.search_all(params).await?;
+
+        // Flatten every collection's hits into scored candidates.
+        let mut candidates: Vec<(String, f32, serde_json::Value)> = Vec::new();
+        if let Some(object) = combined.as_object() {
+            for (source, value) in object {
+                if let Some(array) = value.as_array() {
+                    for item in array {
+                        let score = relevance_score(item, &params.keywords);
+                        candidates.push((source.clone(), score, item.clone()));
+                    }
+                }
+            }
+        }
+
+        // Most relevant first, so the budget is spent on the best snippets.
+        candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        let mut used_tokens = 0u32;
+        let mut dropped = 0u32;
+        let mut context = Vec::new();
+        for (source, score, data) in candidates {
+            let cost = Self::estimate_token_count(&data);
+            if used_tokens + cost <= max_tokens {
+                used_tokens += cost;
+                context.push(serde_json::json!({
+                    "source": source,
+                    "score": score,
+                    "data": data,
+                }));
+            } else {
+                dropped += 1;
+            }
+        }
+
+        Ok(serde_json::json!({
+            "context": context,
+            "metadata": {
+                "used_tokens": used_tokens,
+                "dropped": dropped,
+                "max_tokens": max_tokens,
+            }
+        }))
+    }
+
+    async fn fuzzy_search(
+        &self,
+        query: &str,
+        max_edits: Option<usize>,
+        limit: usize,
+    ) -> Result<serde_json::Value> {
+        let tokens = fuzzy_tokenize(query);
+        if tokens.is_empty() {
+            return Ok(serde_json::json!({
+                "novels": [], "chapters": [], "characters": []
+            }));
+        }
+
+        // Pull a bounded candidate set per collection and rank it in-process.
+        let novels = self.db.get_collection::<Novel>("novels");
+        let chapters = self.db.get_collection::<Chapter>("chapters");
+        let characters = self.db.get_collection::<Character>("characters");
+        let scan = FindOptions::builder().limit(FUZZY_SCAN_LIMIT).build();
+
+        let novel_docs: Vec<Novel> = novels.find(doc! {}, scan.clone()).await?.try_collect().await?;
+        let chapter_docs: Vec<Chapter> =
+            chapters.find(doc! {}, scan.clone()).await?.try_collect().await?;
+        let character_docs: Vec<Character> =
+            characters.find(doc! {}, scan).await?.try_collect().await?;
+
+        let to_values = |docs: Vec<_>| -> Vec<serde_json::Value> {
+            docs.into_iter()
+                .filter_map(|d| serde_json::to_value(d).ok())
+                .collect()
+        };
+
+        Ok(serde_json::json!({
+            "novels": rank_fuzzy(&to_values(novel_docs), NOVEL_FIELDS, &tokens, max_edits, limit),
+            "chapters": rank_fuzzy(&to_values(chapter_docs), CHAPTER_FIELDS, &tokens, max_edits, limit),
+            "characters": rank_fuzzy(&to_values(character_docs), CHARACTER_FIELDS, &tokens, max_edits, limit),
+        }))
+    }
+
+    async fn add_synonym(&self, term: &str, alias: &str) -> Result<()> {
+        let (a, b) = canonical_synonym_pair(term, alias)
+            .ok_or_else(|| McpError::InvalidFilter("synonym pair must be two distinct terms".into()))?;
+        let collection = self.db.get_collection::<Document>("synonyms");
+        // Upsert on the canonical (a, b) ordering so the pair is stored once
+        // regardless of the direction it was added in.
+        let options = mongodb::options::UpdateOptions::builder().upsert(true).build();
+        collection
+            .update_one(doc! { "a": &a, "b": &b }, doc! { "$set": { "a": &a, "b": &b } }, options)
+            .await?;
+        Ok(())
+    }
+
+    async fn remove_synonym(&self, term: &str, alias: &str) -> Result<bool> {
+        let (a, b) = match canonical_synonym_pair(term, alias) {
+            Some(pair) => pair,
+            None => return Ok(false),
+        };
+        let collection = self.db.get_collection::<Document>("synonyms");
+        let result = collection.delete_one(doc! { "a": &a, "b": &b }, None).await?;
+        Ok(result.deleted_count > 0)
+    }
+
+    async fn list_synonyms(&self) -> Result<Vec<(String, String)>> {
+        let collection = self.db.get_collection::<Document>("synonyms");
+        let mut cursor = collection.find(doc! {}, None).await?;
+        let mut pairs = Vec::new();
+        while let Some(doc) = cursor.try_next().await? {
+            if let (Ok(a), Ok(b)) = (doc.get_str("a"), doc.get_str("b")) {
+                pairs.push((a.to_string(), b.to_string()));
+            }
+        }
+        Ok(pairs)
+    }
+
+    async fn load_synonyms(&self) -> Result<crate::services::synonyms::Synonyms> {
+        Ok(crate::services::synonyms::Synonyms::from_pairs(self.list_synonyms().await?))
+    }
+
+    async fn database_stats(&self) -> Result<serde_json::Value> {
+        let database = self.db.database();
+
+        // Per-collection counts, storage size, and newest-document timestamp.
+        let mut collections = serde_json::Map::new();
+        for name in STAT_COLLECTIONS {
+            let collection = self.db.get_collection::<Document>(name);
+            let count = collection.count_documents(doc! {}, None).await?;
+
+            // `collStats` reports the on-disk footprint; tolerate a missing
+            // collection (command errors on an empty database) as zero size.
+            let (size, storage_size) = match database
+                .run_command(doc! { "collStats": name }, None)
+                .await
+            {
+                Ok(stats) => (
+                    stats.get("size").and_then(Bson::as_i64).unwrap_or(0),
+                    stats.get("storageSize").and_then(Bson::as_i64).unwrap_or(0),
+                ),
+                Err(_) => (0, 0),
+            };
+
+            collections.insert(
+                name.to_string(),
+                json!({
+                    "count": count,
+                    "size_bytes": size,
+                    "storage_size_bytes": storage_size,
+                    "newest_timestamp": self.newest_timestamp(name).await?,
+                }),
+            );
+        }
+
+        // Total and average chapter content length, computed server-side.
+        let (total_chapter_length, average_chapter_length) = self.chapter_length_stats().await?;
+
+        let db_stats = database.run_command(doc! { "dbStats": 1 }, None).await.ok();
+        let (data_size, total_storage_size) = db_stats
+            .as_ref()
+            .map(|s| {
+                (
+                    s.get("dataSize").and_then(Bson::as_i64).unwrap_or(0),
+                    s.get("storageSize").and_then(Bson::as_i64).unwrap_or(0),
+                )
+            })
+            .unwrap_or((0, 0));
+
+        Ok(json!({
+            "collections": collections,
+            "chapters_total_length": total_chapter_length,
+            "chapters_average_length": average_chapter_length,
+            "data_size_bytes": data_size,
+            "storage_size_bytes": total_storage_size,
+        }))
+    }
+}
+
+impl MongoDBService {
+    /// RFC 3339 creation timestamp of the most recently inserted document in
+    /// `collection_name`, read from the embedded `_id` time, or `None` when the
+    /// collection is empty.
+    async fn newest_timestamp(&self, collection_name: &str) -> Result<Option<String>> {
+        let collection = self.db.get_collection::<Document>(collection_name);
+        let options = FindOneOptions::builder().sort(doc! { "_id": -1 }).build();
+        let newest = collection.find_one(doc! {}, options).await?;
+        Ok(newest
+            .and_then(|doc| doc.get_object_id("_id").ok())
+            .map(|id| id.timestamp().try_to_rfc3339_string())
+            .transpose()
+            .ok()
+            .flatten())
+    }
+
+    /// Distinct indexed terms of `field` in `collection`, tokenized into
+    /// lowercase words and deduplicated, used to expand a tolerant query leaf
+    /// against the values actually present.
+    async fn indexed_terms(&self, collection: &str, field: &str) -> Result<Vec<String>> {
+        let collection = self.db.get_collection::<Document>(collection);
+        let values = collection.distinct(field, doc! {}, None).await?;
+        let mut terms: Vec<String> = Vec::new();
+        for value in values {
+            if let Some(text) = value.as_str() {
+                for word in fuzzy_tokenize(text) {
+                    if !terms.contains(&word) {
+                        terms.push(word);
+                    }
+                }
+            }
+        }
+        Ok(terms)
+    }
+
+    /// Compile the boolean [`query_tree`](crate::utils::query_tree) for
+    /// `collection` into a Mongo filter when the raw query uses boolean syntax,
+    /// expanding tolerant leaves against the collection's distinct indexed
+    /// terms. Returns `Ok(None)` for a plain keyword query (left to `$text`) or
+    /// an unknown collection.
+    async fn boolean_tree_filter(
+        &self,
+        collection: &str,
+        query: Option<&str>,
+    ) -> Result<Option<Document>> {
+        let query = match query {
+            Some(query) if has_boolean_syntax(query) => query,
+            _ => return Ok(None),
+        };
+        let field = match tree_field(collection) {
+            Some(field) => field,
+            None => return Ok(None),
+        };
+        let dictionary = self.indexed_terms(collection, field).await?;
+        let tree = crate::utils::QueryParser::build_query_tree(query);
+        Ok(Some(tree.compile(field, &dictionary)))
+    }
+
+    /// Sum and mean of chapter `content` lengths (in characters), computed with
+    /// a single aggregation so the full content never leaves the server.
+    async fn chapter_length_stats(&self) -> Result<(i64, f64)> {
+        let chapters = self.db.get_collection::<Document>("chapters");
+        let pipeline = vec![
+            doc! { "$project": { "len": { "$strLenCP": { "$ifNull": ["$content", ""] } } } },
+            doc! { "$group": { "_id": Bson::Null, "total": { "$sum": "$len" }, "average": { "$avg": "$len" } } },
+        ];
+        let mut cursor = chapters.aggregate(pipeline, None).await?;
+        if let Some(doc) = cursor.try_next().await? {
+            let total = doc.get("total").and_then(Bson::as_i64).unwrap_or(0);
+            let average = doc.get("average").and_then(Bson::as_f64).unwrap_or(0.0);
+            Ok((total, average))
+        } else {
+            Ok((0, 0.0))
+        }
+    }
+}
+
+/// Normalise a synonym pair to lowercase and a stable (a, b) ordering so the
+/// unordered pair maps to a single document. Returns `None` if either term is
+/// empty or the two are identical.
+fn canonical_synonym_pair(term: &str, alias: &str) -> Option<(String, String)> {
+    let term = term.trim().to_lowercase();
+    let alias = alias.trim().to_lowercase();
+    if term.is_empty() || alias.is_empty() || term == alias {
+        return None;
+    }
+    if term <= alias {
+        Some((term, alias))
+    } else {
+        Some((alias, term))
+    }
+}
+
+/// Upper bound on documents pulled per collection before fuzzy ranking, so a
+/// broad query stays cheap.
+const FUZZY_SCAN_LIMIT: i64 = 500;
+
+/// A scored field and how it weighs into the composite ranking. `attr` marks
+/// the identifying attribute (title/name) that outranks a body match.
+struct FuzzyField {
+    key: &'static str,
+    weight: f32,
+    attr: bool,
+}
+
+const NOVEL_FIELDS: &[FuzzyField] = &[
+    FuzzyField { key: "title", weight: 3.0, attr: true },
+    FuzzyField { key: "summary", weight: 2.0, attr: false },
+];
+const CHAPTER_FIELDS: &[FuzzyField] = &[
+    FuzzyField { key: "title", weight: 3.0, attr: true },
+    FuzzyField { key: "summary", weight: 2.0, attr: false },
+    FuzzyField { key: "content", weight: 1.0, attr: false },
+];
+const CHARACTER_FIELDS: &[FuzzyField] = &[
+    FuzzyField { key: "name", weight: 3.0, attr: true },
+    FuzzyField { key: "description", weight: 1.0, attr: false },
+];
+
+/// A candidate document plus the bucketed ranking keys used to order it.
+struct Ranked {
+    words_matched: usize,
+    edit_sum: usize,
+    proximity: usize,
+    attr_match: bool,
+    field_weight: f32,
+    doc: serde_json::Value,
+}
+
+impl Ranked {
+    /// A placeholder for a document that matched no query keyword, so it can be
+    /// sorted to the bottom of a reranked page instead of dropped.
+    fn unmatched(doc: serde_json::Value) -> Self {
+        Ranked {
+            words_matched: 0,
+            edit_sum: 0,
+            proximity: 0,
+            attr_match: false,
+            field_weight: 0.0,
+            doc,
+        }
+    }
+
+    /// Collapse the ranking keys into a single scalar in `[0, 1]`: the fraction
+    /// of query words matched, lightly penalised per edit. Used as the caller
+    /// threshold, not the sort key.
+    fn score(&self, query_words: usize) -> f64 {
+        if query_words == 0 {
+            return 0.0;
+        }
+        let matched = self.words_matched as f64 / query_words as f64;
+        (matched - self.edit_sum as f64 * 0.05).clamp(0.0, 1.0)
+    }
+
+    /// The document with its fuzzy `score` attached (no-op for non-objects).
+    fn into_scored(self, query_words: usize) -> serde_json::Value {
+        let score = self.score(query_words);
+        let mut doc = self.doc;
+        if let Some(obj) = doc.as_object_mut() {
+            obj.insert(
+                "score".to_string(),
+                serde_json::json!((score * 1000.0).round() / 1000.0),
+            );
+        }
+        doc
+    }
+}
+
+/// Lower-case, split on non-alphanumerics, and drop empty tokens.
+fn fuzzy_tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_lowercase())
+        .collect()
+}
+
+/// Score `docs` against the query tokens and return them ordered by the
+/// composite ranking, truncated to `limit`. Candidate retrieval accepts a
+/// document token when it lies within the length-scaled edit budget (capped by
+/// `max_edits`); the first query token also matches as a prefix so interactive
+/// queries stay responsive.
+fn rank_fuzzy(
+    docs: &[serde_json::Value],
+    fields: &[FuzzyField],
+    tokens: &[String],
+    max_edits: Option<usize>,
+    limit: usize,
+) -> Vec<serde_json::Value> {
+    let mut ranked: Vec<Ranked> = docs
+        .iter()
+        .filter_map(|doc| score_fuzzy(doc, fields, tokens, max_edits))
+        .collect();
+
+    ranked.sort_by(cmp_ranked);
+
+    ranked.into_iter().take(limit).map(|r| r.doc).collect()
+}
+
+/// Bucketed priority order applied lexicographically: more query words matched
+/// first, then fewer total edits, tighter proximity, a match in the identifying
+/// attribute (title/name), and finally field weight.
+fn cmp_ranked(a: &Ranked, b: &Ranked) -> std::cmp::Ordering {
+    b.words_matched
+        .cmp(&a.words_matched)
+        .then(a.edit_sum.cmp(&b.edit_sum))
+        .then(a.proximity.cmp(&b.proximity))
+        .then(b.attr_match.cmp(&a.attr_match))
+        .then(b.field_weight.total_cmp(&a.field_weight))
+}
+
+/// Reorder a page of serialized documents (`data`, a JSON array) by fuzzy
+/// relevance to `keywords`, attaching a `score` in `[0, 1]` to each object so
+/// callers can threshold. Documents that match no keyword sort last with score
+/// `0` — the page membership set by keyset pagination is preserved, only its
+/// order and scoring change. A no-op when there are no keywords.
+fn apply_fuzzy_ranking(data: &mut serde_json::Value, fields: &[FuzzyField], keywords: &[String]) {
+    if keywords.is_empty() {
+        return;
+    }
+    let array = match data.as_array() {
+        Some(array) if !array.is_empty() => array.clone(),
+        _ => return,
+    };
+    let tokens: Vec<String> = keywords.iter().map(|k| k.to_lowercase()).collect();
+
+    let mut ranked: Vec<Ranked> = array
+        .into_iter()
+        .map(|doc| score_fuzzy(&doc, fields, &tokens, None).unwrap_or_else(|| Ranked::unmatched(doc)))
+        .collect();
+    ranked.sort_by(cmp_ranked);
+
+    *data = serde_json::Value::Array(
+        ranked
+            .into_iter()
+            .map(|r| r.into_scored(tokens.len()))
+            .collect(),
+    );
+}
+
+/// Score one document, or `None` if no query token matched any field.
+fn score_fuzzy(
+    doc: &serde_json::Value,
+    fields: &[FuzzyField],
+    tokens: &[String],
+    max_edits: Option<usize>,
+) -> Option<Ranked> {
+    // Flatten the document's field tokens into one position space so proximity
+    // can be measured as the span covering every matched word.
+    let mut positions: Vec<(usize, bool, f32, String)> = Vec::new();
+    for field in fields {
+        if let Some(text) = doc.get(field.key).and_then(|v| v.as_str()) {
+            for word in fuzzy_tokenize(text) {
+                positions.push((positions.len(), field.attr, field.weight, word));
+            }
+        }
+    }
+
+    let mut words_matched = 0usize;
+    let mut edit_sum = 0usize;
+    let mut attr_match = false;
+    let mut field_weight = 0.0f32;
+    let mut matched_positions = Vec::new();
+
+    for (i, query_token) in tokens.iter().enumerate() {
+        let budget = allowed_edits(query_token.chars().count())
+            .min(max_edits.unwrap_or(usize::MAX));
+        let nfa = LevenshteinNfa::new(query_token, budget);
+
+        // Best hit for this query token: prefer an exact match, then the lowest
+        // edit distance. The first token also matches as a prefix.
+        let mut best: Option<(usize, usize, bool, f32)> = None;
+        for (pos, attr, weight, word) in &positions {
+            let edits = if word == query_token {
+                Some(0)
+            } else if i == 0 && word.starts_with(query_token.as_str()) {
+                Some(0)
+            } else if nfa.accepts(word) {
+                Some(levenshtein(query_token, word))
+            } else {
+                None
+            };
+            if let Some(edits) = edits {
+                let better = match best {
+                    None => true,
+                    Some((_, best_edits, _, _)) => edits < best_edits,
+                };
+                if better {
+                    best = Some((*pos, edits, *attr, *weight));
+                }
+            }
+        }
+
+        if let Some((pos, edits, attr, weight)) = best {
+            words_matched += 1;
+            edit_sum += edits;
+            attr_match |= attr;
+            field_weight = field_weight.max(weight);
+            matched_positions.push(pos);
+        }
+    }
+
+    if words_matched == 0 {
+        return None;
+    }
+
+    let proximity = if matched_positions.len() < 2 {
+        0
+    } else {
+        let min = matched_positions.iter().min().unwrap();
+        let max = matched_positions.iter().max().unwrap();
+        max - min
+    };
+
+    Some(Ranked {
+        words_matched,
+        edit_sum,
+        proximity,
+        attr_match,
+        field_weight,
+        doc: doc.clone(),
+    })
+}
+
+/// Full Levenshtein distance between two words, used to resolve the exact edit
+/// cost of a tolerant match for ranking (the NFA only reports acceptance).
+fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0usize; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j + 1] + 1).min(curr[j] + 1).min(prev[j] + cost);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
+/// Rank a candidate snippet by how many query keywords it mentions. A simple
+/// overlap score is enough to prefer on-topic hits when packing the budget.
+fn relevance_score(item: &serde_json::Value, keywords: &[String]) -> f32 {
+    if keywords.is_empty() {
+        return 0.0;
+    }
+    let haystack = item.to_string().to_lowercase();
+    keywords
+        .iter()
+        .filter(|keyword| haystack.contains(&keyword.to_lowercase()))
+        .count() as f32
 }
\ No newline at end of file
@@ -0,0 +1,77 @@
+//! Embedding generation for semantic search.
+//!
+//! [`Embedder`] decouples vector generation from storage and querying: the
+//! database layer stores and searches `embedding` arrays, while callers plug in
+//! whatever local or remote model produces them. This mirrors how a vector
+//! search SDK keeps the embedder swappable behind a thin trait.
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Produces embedding vectors for one or more input texts.
+#[async_trait]
+pub trait Embedder: Send + Sync {
+    /// Embed a batch of texts, returning one vector per input in order.
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>>;
+}
+
+/// Default dimensionality of a [`HashingEmbedder`] vector.
+pub const DEFAULT_EMBEDDING_DIM: usize = 256;
+
+/// A dependency-free embedder that hashes each token into a fixed-width
+/// bag-of-words vector, so semantic search has a working local default without
+/// a remote model. Deterministic — the same text always embeds to the same
+/// vector — which keeps indexing and querying consistent across runs.
+pub struct HashingEmbedder {
+    dim: usize,
+}
+
+impl HashingEmbedder {
+    /// Build an embedder producing `dim`-dimensional vectors (clamped to at
+    /// least 1).
+    pub fn new(dim: usize) -> Self {
+        Self { dim: dim.max(1) }
+    }
+
+    /// Hash `text` into an L2-normalized term-frequency vector.
+    fn embed_text(&self, text: &str) -> Vec<f32> {
+        let mut vector = vec![0.0f32; self.dim];
+        for token in text.split(|c: char| !c.is_alphanumeric()) {
+            if token.is_empty() {
+                continue;
+            }
+            let bucket = (fnv1a(&token.to_lowercase()) % self.dim as u64) as usize;
+            vector[bucket] += 1.0;
+        }
+        let norm: f32 = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            for value in &mut vector {
+                *value /= norm;
+            }
+        }
+        vector
+    }
+}
+
+impl Default for HashingEmbedder {
+    fn default() -> Self {
+        Self::new(DEFAULT_EMBEDDING_DIM)
+    }
+}
+
+#[async_trait]
+impl Embedder for HashingEmbedder {
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        Ok(texts.iter().map(|text| self.embed_text(text)).collect())
+    }
+}
+
+/// FNV-1a hash, used to bucket a token into the embedding space.
+fn fnv1a(text: &str) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in text.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
@@ -0,0 +1,143 @@
+//! Compact filter-expression DSL compiled to a Mongo query [`Document`].
+//!
+//! The structured fields on [`SearchFilters`](crate::models::SearchFilters) cover
+//! the common cases; [`parse_filter`] lets a client pass the same constraints as
+//! one string — `number >= 10 AND number <= 20 AND tags IN [war, betrayal]` — so
+//! "chapters 10–20 tagged war, excluding flashbacks" is a single field. The
+//! output merges into the filter built from `build_text_search_filter`.
+
+use anyhow::{bail, Context, Result};
+use mongodb::bson::{doc, oid::ObjectId, Bson, Document};
+
+/// Parse a compact filter expression into a Mongo filter document.
+///
+/// Clauses are joined by `AND` (case-insensitive) and take the forms
+/// `field = value`, `field >= value` (and `<=`, `>`, `<`), `field IN [a, b]`,
+/// and `field NOT IN [a, b]`. Range clauses on the same field are merged so
+/// `number >= 10 AND number <= 20` becomes `{ number: { $gte: 10, $lte: 20 } }`.
+pub fn parse_filter(expr: &str) -> Result<Document> {
+    let mut filter = Document::new();
+    for clause in split_and(expr) {
+        let clause = clause.trim();
+        if clause.is_empty() {
+            continue;
+        }
+        let (field, fragment) = parse_clause(clause)?;
+        merge_field(&mut filter, &field, fragment);
+    }
+    Ok(filter)
+}
+
+/// Split an expression on the `AND` keyword, case-insensitively, outside of the
+/// bracketed value lists.
+fn split_and(expr: &str) -> Vec<String> {
+    let mut clauses = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0i32;
+    let words = expr.split_whitespace();
+    for word in words {
+        if word.eq_ignore_ascii_case("and") && depth == 0 {
+            clauses.push(std::mem::take(&mut current));
+            continue;
+        }
+        depth += word.matches('[').count() as i32;
+        depth -= word.matches(']').count() as i32;
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.trim().is_empty() {
+        clauses.push(current);
+    }
+    clauses
+}
+
+/// Parse a single clause into its field name and the query fragment it maps to.
+fn parse_clause(clause: &str) -> Result<(String, Bson)> {
+    // Set membership: `field IN [a, b]` / `field NOT IN [a, b]`.
+    if let Some((field, list)) = split_keyword(clause, "not in") {
+        let values = parse_list(&field, &list)?;
+        return Ok((field, Bson::Document(doc! { "$nin": values })));
+    }
+    if let Some((field, list)) = split_keyword(clause, "in") {
+        let values = parse_list(&field, &list)?;
+        return Ok((field, Bson::Document(doc! { "$in": values })));
+    }
+
+    // Comparison and equality operators, longest first so `>=` beats `>`.
+    for (token, op) in [(">=", "$gte"), ("<=", "$lte"), (">", "$gt"), ("<", "$lt")] {
+        if let Some((field, value)) = clause.split_once(token) {
+            let field = field.trim().to_string();
+            let value = scalar(&field, value.trim());
+            return Ok((field, Bson::Document(doc! { op: value })));
+        }
+    }
+    if let Some((field, value)) = clause.split_once('=') {
+        let field = field.trim().to_string();
+        return Ok((field, scalar(&field, value.trim())));
+    }
+
+    bail!("unrecognized filter clause: `{}`", clause)
+}
+
+/// Split a clause on a keyword surrounded by whitespace, returning the field and
+/// the remainder.
+fn split_keyword(clause: &str, keyword: &str) -> Option<(String, String)> {
+    let lower = clause.to_lowercase();
+    let needle = format!(" {} ", keyword);
+    let pos = lower.find(&needle)?;
+    let field = clause[..pos].trim().to_string();
+    let rest = clause[pos + needle.len()..].trim().to_string();
+    Some((field, rest))
+}
+
+/// Parse a bracketed `[a, b, c]` value list into BSON values.
+fn parse_list(field: &str, raw: &str) -> Result<Vec<Bson>> {
+    let inner = raw
+        .trim()
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .context("set filter must be wrapped in [ ]")?;
+    Ok(inner
+        .split(',')
+        .map(|item| item.trim())
+        .filter(|item| !item.is_empty())
+        .map(|item| scalar(field, item))
+        .collect())
+}
+
+/// Convert a raw token into the most specific BSON scalar, resolving id fields
+/// to `ObjectId`.
+fn scalar(field: &str, token: &str) -> Bson {
+    if field == "_id" || field == "novel_id" || field == "ids" {
+        if let Ok(oid) = ObjectId::parse_str(token) {
+            return Bson::ObjectId(oid);
+        }
+    }
+    if let Ok(value) = token.parse::<i64>() {
+        Bson::Int64(value)
+    } else if let Ok(value) = token.parse::<f64>() {
+        Bson::Double(value)
+    } else if token.eq_ignore_ascii_case("true") || token.eq_ignore_ascii_case("false") {
+        Bson::Boolean(token.eq_ignore_ascii_case("true"))
+    } else {
+        Bson::String(token.to_string())
+    }
+}
+
+/// Insert a fragment for `field`, merging operator maps (e.g. `$gte` and `$lte`)
+/// into a single sub-document rather than overwriting.
+fn merge_field(filter: &mut Document, field: &str, fragment: Bson) {
+    match (filter.get(field).cloned(), fragment) {
+        (Some(Bson::Document(mut existing)), Bson::Document(incoming)) => {
+            for (key, value) in incoming {
+                existing.insert(key, value);
+            }
+            filter.insert(field, existing);
+        }
+        (_, fragment) => {
+            filter.insert(field, fragment);
+        }
+    }
+}
@@ -0,0 +1,253 @@
+//! Fuzzy full-text search over novels, chapters, and characters.
+//!
+//! Where [`VectorSearchService`](crate::services::vector_search::VectorSearchService)
+//! ranks by *meaning*, this service ranks by *words* while tolerating typos. A
+//! MongoDB `$text` index narrows the candidate set; the in-process layer then
+//! tokenizes the query and matches each token against a document's field tokens
+//! within a bounded Levenshtein distance (the same tiers used by the boolean
+//! [`query_tree`](crate::utils::query_tree)). Candidates are ranked by a
+//! composite of matched-word count, match exactness, term proximity, and field
+//! weight (title > synopsis > body), and returned as ranked hits with snippets.
+
+use anyhow::Result;
+
+use futures::TryStreamExt;
+use mongodb::bson::{doc, Document};
+use serde::Serialize;
+
+use crate::db::DatabaseConnection;
+use crate::utils::query_tree::{allowed_edits, LevenshteinNfa};
+
+/// Upper bound on documents scanned per collection when the `$text` prefilter
+/// finds nothing (e.g. the query is all typos), so a miss stays cheap.
+const SCAN_LIMIT: i64 = 500;
+
+/// A weighted field pulled out of a document for scoring.
+struct Field {
+    /// Relative importance; title outranks synopsis outranks body.
+    weight: f32,
+    text: String,
+}
+
+/// Per-collection description of which fields to score and how to weight them.
+struct Source {
+    kind: &'static str,
+    collection: &'static str,
+    /// `(field path, weight)` pairs, highest-weight field first.
+    fields: &'static [(&'static str, f32)],
+}
+
+/// The three retrievable document types and their scored fields.
+const SOURCES: [Source; 3] = [
+    Source {
+        kind: "novel",
+        collection: "novels",
+        fields: &[("title", 3.0), ("summary", 2.0)],
+    },
+    Source {
+        kind: "chapter",
+        collection: "chapters",
+        fields: &[("title", 3.0), ("summary", 2.0), ("content", 1.0)],
+    },
+    Source {
+        kind: "character",
+        collection: "characters",
+        fields: &[("name", 3.0), ("description", 1.0)],
+    },
+];
+
+/// A single ranked search result.
+#[derive(Debug, Clone, Serialize)]
+pub struct Hit {
+    /// Document type: `novel`, `chapter`, or `character`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub document: Document,
+    pub score: f32,
+    /// Highlighted excerpts around the matched terms, best field first.
+    pub snippets: Vec<String>,
+}
+
+/// Answers word-based queries with typo tolerance and composite ranking.
+pub struct FullTextSearchService {
+    db: DatabaseConnection,
+}
+
+impl FullTextSearchService {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Search every source collection and return the top `limit` hits overall,
+    /// ranked by composite score across all types.
+    pub async fn search(&self, query: &str, limit: usize) -> Result<Vec<Hit>> {
+        let tokens = tokenize(query);
+        if tokens.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut hits = Vec::new();
+        for source in &SOURCES {
+            hits.extend(self.search_source(source, &tokens).await?);
+        }
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        hits.truncate(limit);
+        Ok(hits)
+    }
+
+    /// Search a single collection and return the top `limit` hits of that type.
+    pub async fn search_kind(&self, kind: &str, query: &str, limit: usize) -> Result<Vec<Hit>> {
+        let tokens = tokenize(query);
+        let source = SOURCES.iter().find(|s| s.kind == kind);
+        let (Some(source), false) = (source, tokens.is_empty()) else {
+            return Ok(Vec::new());
+        };
+        let mut hits = self.search_source(source, &tokens).await?;
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        hits.truncate(limit);
+        Ok(hits)
+    }
+
+    /// Narrow candidates with the `$text` index, then score them in-process.
+    /// A `$text` miss (all-typo queries the index can't catch) falls back to a
+    /// capped collection scan so fuzzy matching still has documents to rank.
+    async fn search_source(&self, source: &Source, tokens: &[String]) -> Result<Vec<Hit>> {
+        let coll = self.db.get_collection::<Document>(source.collection);
+
+        let text_query = tokens.join(" ");
+        let candidates: Vec<Document> =
+            match coll.find(doc! { "$text": { "$search": &text_query } }, None).await {
+                Ok(cursor) => cursor.try_collect().await.unwrap_or_default(),
+                Err(_) => Vec::new(),
+            };
+
+        let candidates = if candidates.is_empty() {
+            let options = mongodb::options::FindOptions::builder()
+                .limit(SCAN_LIMIT)
+                .build();
+            coll.find(doc! {}, options).await?.try_collect().await?
+        } else {
+            candidates
+        };
+
+        let mut hits = Vec::new();
+        for document in candidates {
+            if let Some(hit) = score_document(source, &document, tokens) {
+                hits.push(hit);
+            }
+        }
+        Ok(hits)
+    }
+}
+
+/// Lower-case, split on non-alphanumerics, and drop empty tokens.
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_lowercase())
+        .collect()
+}
+
+/// Score one document against the query tokens, or `None` if nothing matched.
+fn score_document(source: &Source, document: &Document, tokens: &[String]) -> Option<Hit> {
+    let fields: Vec<Field> = source
+        .fields
+        .iter()
+        .filter_map(|(path, weight)| {
+            document.get_str(path).ok().map(|text| Field {
+                weight: *weight,
+                text: text.to_string(),
+            })
+        })
+        .collect();
+
+    let mut total = 0.0f32;
+    let mut matched_words = 0usize;
+    let mut snippets = Vec::new();
+
+    for field in &fields {
+        let field_tokens = tokenize(&field.text);
+        let mut field_score = 0.0f32;
+        let mut matched_positions = Vec::new();
+
+        for query_token in tokens {
+            let max_edits = allowed_edits(query_token.chars().count());
+            let nfa = LevenshteinNfa::new(query_token, max_edits);
+            // Best hit for this query token: the first exact occurrence, else
+            // the first typo-tolerant one.
+            let best = field_tokens
+                .iter()
+                .enumerate()
+                .filter(|(_, word)| *word == query_token || nfa.accepts(word))
+                .map(|(pos, word)| (pos, word == query_token))
+                .min_by_key(|&(_, exact)| !exact);
+            if let Some((pos, exact)) = best {
+                // Exact matches outrank typo matches within the same field.
+                field_score += if exact { 1.0 } else { 0.6 };
+                matched_positions.push(pos);
+            }
+        }
+
+        if !matched_positions.is_empty() {
+            matched_words += matched_positions.len();
+            total += field.weight * (field_score + proximity_bonus(&matched_positions));
+            snippets.push(make_snippet(&field_tokens, matched_positions[0]));
+        }
+    }
+
+    if matched_words == 0 {
+        return None;
+    }
+
+    // Matched-word coverage dominates the composite so a document hitting more
+    // of the query ranks above one that merely hit a high-weight field.
+    let score = matched_words as f32 * 10.0 + total;
+    Some(Hit {
+        kind: source.kind.to_string(),
+        document: document.clone(),
+        score,
+        snippets,
+    })
+}
+
+/// Reward matched terms that cluster together: the tighter the span covering
+/// every matched position, the larger the bonus (zero for a single match).
+fn proximity_bonus(positions: &[usize]) -> f32 {
+    if positions.len() < 2 {
+        return 0.0;
+    }
+    let min = positions.iter().min().unwrap();
+    let max = positions.iter().max().unwrap();
+    let span = (max - min) as f32;
+    positions.len() as f32 / (1.0 + span)
+}
+
+/// Build a short excerpt centred on the word at `word_index`, wrapping the
+/// matched word in `**…**` so clients can highlight it. Operates on the same
+/// [`tokenize`] output the match positions index into, so the highlighted word
+/// is always the one that matched rather than a misaligned neighbour.
+fn make_snippet(words: &[String], word_index: usize) -> String {
+    if words.is_empty() {
+        return String::new();
+    }
+    let window = 8;
+    let start = word_index.saturating_sub(window);
+    let end = (word_index + window + 1).min(words.len());
+
+    let mut parts: Vec<String> = Vec::new();
+    for (i, word) in words[start..end].iter().enumerate() {
+        if start + i == word_index {
+            parts.push(format!("**{}**", word));
+        } else {
+            parts.push(word.to_string());
+        }
+    }
+    let mut snippet = parts.join(" ");
+    if start > 0 {
+        snippet.insert_str(0, "… ");
+    }
+    if end < words.len() {
+        snippet.push_str(" …");
+    }
+    snippet
+}
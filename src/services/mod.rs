@@ -1,5 +1,15 @@
+pub mod analytics;
+pub mod cache;
+pub mod cover_service;
 pub mod crud_service;
 pub mod db_service;
+pub mod embedding;
+pub mod filter_dsl;
+pub mod fulltext_search;
+pub mod page_token;
+pub mod pagination;
+pub mod synonyms;
+pub mod vector_search;
 
 pub use db_service::{DatabaseService};
 pub use crud_service::{
@@ -0,0 +1,122 @@
+//! Opaque keyset pagination tokens for the [`DatabaseService`](super::db_service::DatabaseService)
+//! search path.
+//!
+//! The search methods page by anchoring on the last returned document's sort key
+//! rather than a numeric `skip`, so advancing stays O(1) as the collection grows.
+//! The anchor — collection, page limit, sort field, and last value — is packed
+//! into a BSON document and base64url-encoded into an opaque string. Decoding
+//! validates the structure and the collection/limit it was minted for, so a
+//! tampered or cross-collection token is rejected with a clear error rather than
+//! silently returning the wrong page.
+
+use anyhow::{bail, Context, Result};
+use mongodb::bson::{doc, Bson, Document};
+
+/// The decoded contents of a page token.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageToken {
+    pub collection: String,
+    pub limit: u32,
+    pub field: String,
+    pub value: Bson,
+}
+
+impl PageToken {
+    pub fn new(collection: &str, limit: u32, field: &str, value: Bson) -> Self {
+        Self {
+            collection: collection.to_string(),
+            limit,
+            field: field.to_string(),
+            value,
+        }
+    }
+
+    /// Encode the token as an opaque base64url string.
+    pub fn encode(&self) -> String {
+        let document = doc! {
+            "c": &self.collection,
+            "l": self.limit,
+            "f": &self.field,
+            "v": self.value.clone(),
+        };
+        let bytes = mongodb::bson::to_vec(&document).unwrap_or_default();
+        base64url_encode(&bytes)
+    }
+
+    /// Decode and validate a token, ensuring it was minted for `collection` with
+    /// the same page `limit`. Returns an error on a malformed or mismatched token.
+    pub fn decode(token: &str, collection: &str, limit: u32) -> Result<PageToken> {
+        let bytes = base64url_decode(token).context("invalid pagination token")?;
+        let document: Document =
+            mongodb::bson::from_slice(&bytes).context("malformed pagination token")?;
+
+        let decoded = PageToken {
+            collection: document.get_str("c").context("token missing collection")?.to_string(),
+            limit: document.get_i32("l").context("token missing limit")? as u32,
+            field: document.get_str("f").context("token missing sort field")?.to_string(),
+            value: document.get("v").cloned().context("token missing anchor value")?,
+        };
+
+        if decoded.collection != collection {
+            bail!("pagination token is for a different collection");
+        }
+        if decoded.limit != limit {
+            bail!("pagination token was issued for a different page size");
+        }
+        Ok(decoded)
+    }
+
+    /// The range predicate that selects documents strictly after this anchor.
+    pub fn predicate(&self) -> Document {
+        doc! { &self.field: { "$gt": self.value.clone() } }
+    }
+}
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Encode bytes as unpadded base64url.
+pub(crate) fn base64url_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity((bytes.len() + 2) / 3 * 4);
+    for chunk in bytes.chunks(3) {
+        let b0 = chunk[0] as u32;
+        let b1 = *chunk.get(1).unwrap_or(&0) as u32;
+        let b2 = *chunk.get(2).unwrap_or(&0) as u32;
+        let n = (b0 << 16) | (b1 << 8) | b2;
+        out.push(ALPHABET[(n >> 18) as usize & 0x3f] as char);
+        out.push(ALPHABET[(n >> 12) as usize & 0x3f] as char);
+        if chunk.len() > 1 {
+            out.push(ALPHABET[(n >> 6) as usize & 0x3f] as char);
+        }
+        if chunk.len() > 2 {
+            out.push(ALPHABET[n as usize & 0x3f] as char);
+        }
+    }
+    out
+}
+
+/// Decode unpadded base64url, rejecting any character outside the alphabet.
+pub(crate) fn base64url_decode(text: &str) -> Result<Vec<u8>> {
+    let value_of = |c: u8| -> Option<u32> {
+        ALPHABET.iter().position(|&a| a == c).map(|p| p as u32)
+    };
+
+    let mut out = Vec::with_capacity(text.len() / 4 * 3);
+    for chunk in text.as_bytes().chunks(4) {
+        if chunk.len() < 2 {
+            bail!("pagination token has a truncated group");
+        }
+        let mut n = 0u32;
+        for (i, &c) in chunk.iter().enumerate() {
+            let v = value_of(c).context("pagination token has an invalid character")?;
+            n |= v << (18 - 6 * i);
+        }
+        out.push((n >> 16) as u8);
+        if chunk.len() > 2 {
+            out.push((n >> 8) as u8);
+        }
+        if chunk.len() > 3 {
+            out.push(n as u8);
+        }
+    }
+    Ok(out)
+}
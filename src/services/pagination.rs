@@ -0,0 +1,225 @@
+//! Keyset (cursor) pagination shared by the [`CrudService`](super::crud_service::CrudService)
+//! implementations.
+//!
+//! Rather than `skip`, which drifts as documents are inserted concurrently,
+//! pages are anchored on the BSON values of the sort keys (with `_id` as a
+//! final tiebreaker) of the last returned document. The opaque [`Cursor`]
+//! carries those values so the next call translates them into a compound range
+//! predicate merged into the caller's filter.
+
+use anyhow::{Context, Result};
+use futures::TryStreamExt;
+use mongodb::bson::{doc, Bson, Document};
+use mongodb::options::FindOptions;
+use serde::de::DeserializeOwned;
+
+use crate::db::DatabaseConnection;
+
+/// Sort direction for a single key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Order {
+    Asc,
+    Desc,
+}
+
+impl Order {
+    fn mongo_value(self) -> i32 {
+        match self {
+            Order::Asc => 1,
+            Order::Desc => -1,
+        }
+    }
+
+    /// The range operator that selects documents *after* the cursor anchor.
+    fn after_operator(self) -> &'static str {
+        match self {
+            Order::Asc => "$gt",
+            Order::Desc => "$lt",
+        }
+    }
+}
+
+/// A model's sortable fields, mapping each variant to its stored BSON field.
+pub trait SortField: Copy {
+    fn field(&self) -> &'static str;
+}
+
+/// An opaque pagination token. Internally the hex of a BSON document holding
+/// the anchor document's sort-key values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cursor(pub String);
+
+/// Options for a paged read.
+pub struct ListOptions<K: SortField> {
+    pub sort: Vec<(K, Order)>,
+    pub limit: Option<i64>,
+    pub after: Option<Cursor>,
+}
+
+impl<K: SortField> Default for ListOptions<K> {
+    fn default() -> Self {
+        Self { sort: Vec::new(), limit: None, after: None }
+    }
+}
+
+/// A page of results plus the cursor to fetch the next page, if any.
+pub type Page<T> = (Vec<T>, Option<Cursor>);
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+
+/// Fetch one keyset page from `collection_name` in insertion-independent order.
+pub async fn fetch_page<T>(
+    db: &DatabaseConnection,
+    collection_name: &str,
+    user_filter: Document,
+    sort: &[(&str, Order)],
+    limit: Option<i64>,
+    after: Option<&Cursor>,
+) -> Result<Page<T>>
+where
+    T: DeserializeOwned + Send + Sync + Unpin,
+{
+    // Always break ties on `_id` so the ordering is total and the cursor is
+    // unambiguous even when the user's sort keys collide.
+    let mut order: Vec<(String, Order)> = sort.iter().map(|(f, o)| (f.to_string(), *o)).collect();
+    if !order.iter().any(|(f, _)| f == "_id") {
+        order.push(("_id".to_string(), Order::Asc));
+    }
+
+    let mut filter = user_filter;
+    if let Some(cursor) = after {
+        let anchor = decode_cursor(cursor)?;
+        filter = merge_and(filter, keyset_predicate(&order, &anchor));
+    }
+
+    let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE);
+    let options = FindOptions::builder()
+        .sort(sort_document(&order))
+        .limit(limit)
+        .build();
+
+    let collection = db.get_collection::<Document>(collection_name);
+    let cursor = collection.find(filter, options).await?;
+    let raw: Vec<Document> = cursor.try_collect().await?;
+
+    // A full page means there may be more; hand back a cursor anchored on the
+    // last document. A short page is the end of the result set.
+    let next = if raw.len() as i64 == limit {
+        raw.last().map(|doc| encode_cursor(&anchor_document(&order, doc)))
+    } else {
+        None
+    };
+
+    let items = raw
+        .into_iter()
+        .map(|doc| mongodb::bson::from_document(doc).context("deserializing paged document"))
+        .collect::<Result<Vec<T>>>()?;
+
+    Ok((items, next))
+}
+
+fn sort_document(order: &[(String, Order)]) -> Document {
+    let mut sort = Document::new();
+    for (field, dir) in order {
+        sort.insert(field.clone(), dir.mongo_value());
+    }
+    sort
+}
+
+/// Build the compound keyset predicate: an `$or` over "ties on the first i-1
+/// keys, strictly past the anchor on key i".
+fn keyset_predicate(order: &[(String, Order)], anchor: &Document) -> Document {
+    let mut clauses: Vec<Bson> = Vec::with_capacity(order.len());
+    for i in 0..order.len() {
+        let mut clause = Document::new();
+        for (field, _) in &order[..i] {
+            clause.insert(field.clone(), anchor.get(field).cloned().unwrap_or(Bson::Null));
+        }
+        let (field, dir) = &order[i];
+        let anchor_value = anchor.get(field).cloned().unwrap_or(Bson::Null);
+        clause.insert(field.clone(), doc! { dir.after_operator(): anchor_value });
+        clauses.push(Bson::Document(clause));
+    }
+    doc! { "$or": clauses }
+}
+
+fn anchor_document(order: &[(String, Order)], doc: &Document) -> Document {
+    let mut anchor = Document::new();
+    for (field, _) in order {
+        if let Some(value) = doc.get(field) {
+            anchor.insert(field.clone(), value.clone());
+        }
+    }
+    anchor
+}
+
+fn merge_and(user_filter: Document, keyset: Document) -> Document {
+    if user_filter.is_empty() {
+        keyset
+    } else {
+        doc! { "$and": [Bson::Document(user_filter), Bson::Document(keyset)] }
+    }
+}
+
+fn encode_cursor(anchor: &Document) -> Cursor {
+    let bytes = mongodb::bson::to_vec(anchor).unwrap_or_default();
+    Cursor(to_hex(&bytes))
+}
+
+fn decode_cursor(cursor: &Cursor) -> Result<Document> {
+    let bytes = from_hex(&cursor.0).context("decoding cursor token")?;
+    mongodb::bson::from_slice(&bytes).context("parsing cursor token")
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        out.push_str(&format!("{:02x}", byte));
+    }
+    out
+}
+
+fn from_hex(hex: &str) -> Result<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        anyhow::bail!("cursor token has odd length");
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).context("invalid cursor hex"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_round_trips_through_hex_encoding() {
+        let anchor = doc! { "_id": 42i64, "title": "dragons" };
+        let cursor = encode_cursor(&anchor);
+        let decoded = decode_cursor(&cursor).unwrap();
+        assert_eq!(decoded, anchor);
+    }
+
+    #[test]
+    fn decode_rejects_an_odd_length_token() {
+        assert!(from_hex("abc").is_err());
+    }
+
+    #[test]
+    fn decode_rejects_non_hex_input() {
+        assert!(from_hex("zz").is_err());
+    }
+
+    #[test]
+    fn keyset_predicate_requires_ties_on_earlier_keys_and_a_strict_bound_on_the_current_one() {
+        let order = vec![("novel_id".to_string(), Order::Asc), ("_id".to_string(), Order::Asc)];
+        let anchor = doc! { "novel_id": "n1", "_id": 7i64 };
+        let predicate = keyset_predicate(&order, &anchor);
+        let clauses = predicate.get_array("$or").unwrap();
+        assert_eq!(clauses.len(), 2);
+        let second = clauses[1].as_document().unwrap();
+        assert_eq!(second.get_str("novel_id").unwrap(), "n1");
+        assert_eq!(second.get_document("_id").unwrap().get_i64("$gt").unwrap(), 7);
+    }
+}
@@ -0,0 +1,68 @@
+//! Bidirectional synonym map consulted by [`QueryParser`](crate::utils::QueryParser)
+//! before a search runs, so a character or place known by a nickname resolves
+//! to its canonical name and vice versa.
+//!
+//! The pairs are persisted one-per-document in the `synonyms` collection and
+//! loaded into this in-memory map at startup (see
+//! [`DatabaseService::load_synonyms`](crate::services::db_service::DatabaseService::load_synonyms)),
+//! which the handler caches. Expansion is one hop — a term yields itself plus
+//! every term directly linked to it — which is enough for the "Kal" ↔
+//! "Kaladin" nickname case without risking a runaway transitive closure.
+
+use std::collections::{HashMap, HashSet};
+
+/// A case-insensitive, bidirectional map of interchangeable terms.
+#[derive(Debug, Clone, Default)]
+pub struct Synonyms {
+    links: HashMap<String, HashSet<String>>,
+}
+
+impl Synonyms {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a map from `(term, alias)` pairs, linking each pair in both
+    /// directions.
+    pub fn from_pairs<I>(pairs: I) -> Self
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let mut map = Self::new();
+        for (term, alias) in pairs {
+            map.link(&term, &alias);
+        }
+        map
+    }
+
+    /// Record that `term` and `alias` are interchangeable. Self-links and empty
+    /// terms are ignored.
+    pub fn link(&mut self, term: &str, alias: &str) {
+        let term = term.trim().to_lowercase();
+        let alias = alias.trim().to_lowercase();
+        if term.is_empty() || alias.is_empty() || term == alias {
+            return;
+        }
+        self.links.entry(term.clone()).or_default().insert(alias.clone());
+        self.links.entry(alias).or_default().insert(term);
+    }
+
+    /// `term` plus every term directly linked to it, lowercased and
+    /// deduplicated. A term with no synonyms yields just itself.
+    pub fn expand(&self, term: &str) -> Vec<String> {
+        let term = term.to_lowercase();
+        let mut out = vec![term.clone()];
+        if let Some(aliases) = self.links.get(&term) {
+            for alias in aliases {
+                if !out.contains(alias) {
+                    out.push(alias.clone());
+                }
+            }
+        }
+        out
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.links.is_empty()
+    }
+}
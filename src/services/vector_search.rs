@@ -0,0 +1,356 @@
+//! Semantic / vector search over novels, chapters, and QA.
+//!
+//! The literal search paths in [`DatabaseService`](crate::services::DatabaseService)
+//! match on characters; this service matches on *meaning*. Each indexed document
+//! carries an `embedding` vector, and a query is answered by embedding the query
+//! text with the same [`Embedder`] and ranking documents by vector similarity.
+//!
+//! On Atlas the ranking is pushed into the database via `$vectorSearch`; on a
+//! self-hosted MongoDB without a vector index the same cosine ranking is computed
+//! in Rust. Long chapter `content` is cut into overlapping windows by [`Splitter`]
+//! so each window embeds independently and back-references its parent chapter,
+//! which is what turns this into a RAG-capable retrieval layer.
+
+use anyhow::Result;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use futures::TryStreamExt;
+use mongodb::bson::{doc, oid::ObjectId, Bson, Document};
+use serde::{Deserialize, Serialize};
+
+use crate::db::DatabaseConnection;
+use crate::services::embedding::Embedder;
+
+/// The Atlas vector index name queried by [`VectorSearchService::semantic_search`].
+const VECTOR_INDEX_NAME: &str = "embedding_index";
+
+/// A contiguous window of chapter `content`, embedded on its own and linked back
+/// to the chapter it was cut from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Chunk {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub chapter_id: ObjectId,
+    /// Zero-based position of this window within its parent chapter.
+    pub sequence: usize,
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<Vec<f32>>,
+}
+
+/// A search hit: the matched document (minus its raw vector) and its score.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScoredHit {
+    pub document: Document,
+    pub score: f32,
+}
+
+/// Cuts long text into overlapping windows so each window embeds independently.
+#[derive(Debug, Clone)]
+pub struct Splitter {
+    window: usize,
+    overlap: usize,
+}
+
+impl Splitter {
+    /// Build a splitter with a `window`-word window sharing `overlap` words with
+    /// the previous window. `window` is clamped to at least 1 and `overlap`
+    /// below `window` so the cursor always advances.
+    pub fn new(window: usize, overlap: usize) -> Self {
+        let window = window.max(1);
+        let overlap = overlap.min(window.saturating_sub(1));
+        Self { window, overlap }
+    }
+
+    /// Split `text` into whitespace-delimited overlapping windows.
+    pub fn split(&self, text: &str) -> Vec<String> {
+        let words: Vec<&str> = text.split_whitespace().collect();
+        if words.is_empty() {
+            return Vec::new();
+        }
+        if words.len() <= self.window {
+            return vec![words.join(" ")];
+        }
+
+        let step = self.window - self.overlap;
+        let mut windows = Vec::new();
+        let mut start = 0;
+        while start < words.len() {
+            let end = (start + self.window).min(words.len());
+            windows.push(words[start..end].join(" "));
+            if end == words.len() {
+                break;
+            }
+            start += step;
+        }
+        windows
+    }
+}
+
+impl Default for Splitter {
+    fn default() -> Self {
+        Self::new(200, 40)
+    }
+}
+
+/// Answers meaning-based queries by embedding text and ranking stored vectors.
+pub struct VectorSearchService {
+    db: DatabaseConnection,
+    embedder: Arc<dyn Embedder>,
+    splitter: Splitter,
+    /// When `false`, similarity is ranked in Rust instead of via Atlas
+    /// `$vectorSearch` (for self-hosted MongoDB without a vector index).
+    atlas: bool,
+}
+
+impl VectorSearchService {
+    pub fn new(db: DatabaseConnection, embedder: Arc<dyn Embedder>, atlas: bool) -> Self {
+        Self {
+            db,
+            embedder,
+            splitter: Splitter::default(),
+            atlas,
+        }
+    }
+
+    /// Override the default chunking window/overlap.
+    pub fn with_splitter(mut self, splitter: Splitter) -> Self {
+        self.splitter = splitter;
+        self
+    }
+
+    /// Embed a single text, unwrapping the batch result.
+    async fn embed_one(&self, text: &str) -> Result<Vec<f32>> {
+        self.embedder
+            .embed(&[text.to_string()])
+            .await?
+            .pop()
+            .ok_or_else(|| anyhow::anyhow!("embedder returned no vector"))
+    }
+
+    /// Split a chapter's `content` into overlapping windows, embed each, and
+    /// persist them as standalone `chunks` documents for retrieval. Returns the
+    /// number of chunks written.
+    pub async fn index_chapter(&self, chapter_id: ObjectId, content: &str) -> Result<usize> {
+        let windows = self.splitter.split(content);
+        if windows.is_empty() {
+            return Ok(0);
+        }
+
+        let embeddings = self.embedder.embed(&windows).await?;
+        let chunks: Vec<Chunk> = windows
+            .into_iter()
+            .zip(embeddings)
+            .enumerate()
+            .map(|(sequence, (text, embedding))| Chunk {
+                id: None,
+                chapter_id,
+                sequence,
+                text,
+                embedding: Some(embedding),
+            })
+            .collect();
+
+        let count = chunks.len();
+        let collection = self.db.get_collection::<Chunk>("chunks");
+        collection.insert_many(chunks, None).await?;
+        Ok(count)
+    }
+
+    /// Return the top-`k` documents in `collection` most similar in meaning to
+    /// `query_text`, each paired with its similarity score.
+    pub async fn semantic_search(
+        &self,
+        collection: &str,
+        query_text: &str,
+        k: usize,
+    ) -> Result<Vec<ScoredHit>> {
+        let query_vector = self.embed_one(query_text).await?;
+        if self.atlas {
+            self.atlas_search(collection, &query_vector, k).await
+        } else {
+            self.local_search(collection, &query_vector, k).await
+        }
+    }
+
+    async fn atlas_search(
+        &self,
+        collection: &str,
+        query_vector: &[f32],
+        k: usize,
+    ) -> Result<Vec<ScoredHit>> {
+        let pipeline = vec![
+            doc! {
+                "$vectorSearch": {
+                    "index": VECTOR_INDEX_NAME,
+                    "path": "embedding",
+                    "queryVector": to_bson_vector(query_vector),
+                    "numCandidates": (k as i64 * 10).max(100),
+                    "limit": k as i64,
+                }
+            },
+            doc! { "$set": { "__score": { "$meta": "vectorSearchScore" } } },
+            doc! { "$project": { "embedding": 0 } },
+        ];
+
+        let coll = self.db.get_collection::<Document>(collection);
+        let mut cursor = coll.aggregate(pipeline, None).await?;
+        let mut hits = Vec::new();
+        while let Some(doc) = cursor.try_next().await? {
+            hits.push(hit_from_doc(doc));
+        }
+        Ok(hits)
+    }
+
+    /// Non-Atlas fallback: stream every embedded document and rank in memory.
+    async fn local_search(
+        &self,
+        collection: &str,
+        query_vector: &[f32],
+        k: usize,
+    ) -> Result<Vec<ScoredHit>> {
+        let coll = self.db.get_collection::<Document>(collection);
+        let filter = doc! { "embedding": { "$exists": true } };
+        let mut cursor = coll.find(filter, None).await?;
+
+        let mut scored: Vec<ScoredHit> = Vec::new();
+        while let Some(mut document) = cursor.try_next().await? {
+            if let Some(embedding) = extract_embedding(&document) {
+                let score = cosine_similarity(query_vector, &embedding);
+                document.remove("embedding");
+                scored.push(ScoredHit { document, score });
+            }
+        }
+
+        scored.sort_by(|a, b| b.score.total_cmp(&a.score));
+        scored.truncate(k);
+        Ok(scored)
+    }
+
+    /// Blend lexical `$text` relevance with vector similarity. `alpha` weights
+    /// the vector score and `1.0 - alpha` the text score; both are scaled into
+    /// `[0, 1]` before combining so one leg cannot dominate by raw magnitude.
+    pub async fn hybrid_search(
+        &self,
+        collection: &str,
+        query_text: &str,
+        k: usize,
+        alpha: f32,
+    ) -> Result<Vec<ScoredHit>> {
+        let alpha = alpha.clamp(0.0, 1.0);
+
+        // Over-fetch both legs. Only the vector leg's hits are blended; each
+        // takes a text score when the text leg also returned it.
+        let vector_hits = self.semantic_search(collection, query_text, k * 2).await?;
+        let text_scores = self.text_scores(collection, query_text, k * 2).await?;
+
+        let vector_max = vector_hits
+            .iter()
+            .map(|hit| hit.score)
+            .fold(0.0f32, f32::max);
+        let text_max = text_scores.values().copied().fold(0.0f32, f32::max);
+
+        let mut combined: Vec<ScoredHit> = vector_hits
+            .into_iter()
+            .map(|mut hit| {
+                let vector = normalize(hit.score, vector_max);
+                let text = hit
+                    .document
+                    .get_object_id("_id")
+                    .ok()
+                    .and_then(|id| text_scores.get(&id).copied())
+                    .map(|score| normalize(score, text_max))
+                    .unwrap_or(0.0);
+                hit.score = alpha * vector + (1.0 - alpha) * text;
+                hit
+            })
+            .collect();
+
+        combined.sort_by(|a, b| b.score.total_cmp(&a.score));
+        combined.truncate(k);
+        Ok(combined)
+    }
+
+    /// Collect `$text` relevance scores keyed by document id.
+    async fn text_scores(
+        &self,
+        collection: &str,
+        query_text: &str,
+        limit: usize,
+    ) -> Result<HashMap<ObjectId, f32>> {
+        let pipeline = vec![
+            doc! { "$match": { "$text": { "$search": query_text } } },
+            doc! { "$set": { "__score": { "$meta": "textScore" } } },
+            doc! { "$sort": { "__score": -1 } },
+            doc! { "$limit": limit as i64 },
+            doc! { "$project": { "__score": 1 } },
+        ];
+
+        let coll = self.db.get_collection::<Document>(collection);
+        let mut cursor = coll.aggregate(pipeline, None).await?;
+        let mut scores = HashMap::new();
+        while let Some(document) = cursor.try_next().await? {
+            if let Ok(id) = document.get_object_id("_id") {
+                let score = document.get_f64("__score").unwrap_or(0.0) as f32;
+                scores.insert(id, score);
+            }
+        }
+        Ok(scores)
+    }
+}
+
+/// Lift the `__score` field out of an aggregation document into a [`ScoredHit`].
+fn hit_from_doc(mut document: Document) -> ScoredHit {
+    let score = document
+        .remove("__score")
+        .and_then(|value| value.as_f64())
+        .unwrap_or(0.0) as f32;
+    ScoredHit { document, score }
+}
+
+/// Read an `embedding` array of BSON doubles out of a raw document.
+fn extract_embedding(document: &Document) -> Option<Vec<f32>> {
+    let array = document.get_array("embedding").ok()?;
+    Some(
+        array
+            .iter()
+            .filter_map(|value| value.as_f64().map(|f| f as f32))
+            .collect(),
+    )
+}
+
+/// Encode an `f32` vector as a BSON array of doubles for `$vectorSearch`.
+fn to_bson_vector(embedding: &[f32]) -> Bson {
+    Bson::Array(
+        embedding
+            .iter()
+            .map(|value| Bson::Double(*value as f64))
+            .collect(),
+    )
+}
+
+/// Scale a raw score into `[0, 1]` by its batch maximum; `0.0` when `max` is 0.
+fn normalize(score: f32, max: f32) -> f32 {
+    if max <= 0.0 {
+        0.0
+    } else {
+        score / max
+    }
+}
+
+/// Cosine similarity between two vectors; `0.0` when either has zero magnitude.
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let mut dot = 0.0f32;
+    let mut norm_a = 0.0f32;
+    let mut norm_b = 0.0f32;
+    for (x, y) in a.iter().zip(b.iter()) {
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a.sqrt() * norm_b.sqrt())
+}
@@ -0,0 +1,273 @@
+//! Typed predicate AST compiled to Mongo filter documents.
+//!
+//! [`QueryParser`](crate::utils::query_parser::QueryParser) historically pulled
+//! filters out of the query string with a handful of hand-written regexes that
+//! could only express equality on `novel_id`, `character_name`, and `tags`. This
+//! module adds, alongside that extraction, a small typed AST — [`Predicate`] — that compiles to
+//! a Mongo [`Document`] and validates every field against the target model's
+//! schema, so a malformed query fails fast with a [`FilterError`] instead of
+//! silently matching nothing.
+
+use std::fmt;
+
+use mongodb::bson::{doc, oid::ObjectId, Bson, Document};
+
+/// Known field sets per collection, used to reject unknown fields.
+const NOVEL_FIELDS: &[&str] = &[
+    "title", "author", "summary", "tags", "genre", "word_count", "language",
+    "publication_date",
+];
+const CHAPTER_FIELDS: &[&str] = &["novel_id", "number", "title", "summary", "key_points", "content"];
+const CHARACTER_FIELDS: &[&str] = &["novel_id", "name", "role", "description", "key_traits"];
+const QA_FIELDS: &[&str] = &["novel_id", "question", "answer", "tags"];
+
+/// A scalar value appearing in a predicate, parsed from the query string.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Scalar {
+    Str(String),
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+}
+
+impl Scalar {
+    /// Parse a raw token into the most specific scalar it represents.
+    pub fn parse(token: &str) -> Scalar {
+        if let Ok(value) = token.parse::<i64>() {
+            Scalar::Int(value)
+        } else if let Ok(value) = token.parse::<f64>() {
+            Scalar::Float(value)
+        } else if token.eq_ignore_ascii_case("true") || token.eq_ignore_ascii_case("false") {
+            Scalar::Bool(token.eq_ignore_ascii_case("true"))
+        } else {
+            Scalar::Str(token.to_string())
+        }
+    }
+
+    fn to_bson(&self, field: &str) -> Bson {
+        // `novel_id` is the one field whose string form is an ObjectId.
+        if field == "novel_id" {
+            if let Scalar::Str(s) = self {
+                if let Ok(oid) = ObjectId::parse_str(s) {
+                    return Bson::ObjectId(oid);
+                }
+            }
+        }
+        match self {
+            Scalar::Str(s) => Bson::String(s.clone()),
+            Scalar::Int(i) => Bson::Int64(*i),
+            Scalar::Float(f) => Bson::Double(*f),
+            Scalar::Bool(b) => Bson::Boolean(*b),
+        }
+    }
+}
+
+/// A typed filter predicate that compiles to a Mongo query fragment.
+#[derive(Debug, Clone)]
+pub enum Predicate {
+    Eq(String, Scalar),
+    In(String, Vec<Scalar>),
+    Regex(String, String),
+    /// Numeric range with any combination of inclusive/exclusive bounds.
+    Range {
+        field: String,
+        gt: Option<Scalar>,
+        gte: Option<Scalar>,
+        lt: Option<Scalar>,
+        lte: Option<Scalar>,
+    },
+    Exists(String, bool),
+    And(Vec<Predicate>),
+    Or(Vec<Predicate>),
+    Not(Box<Predicate>),
+}
+
+/// Raised when a predicate references a field outside its model's schema.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterError {
+    UnknownField { collection: String, field: String },
+}
+
+impl fmt::Display for FilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FilterError::UnknownField { collection, field } => {
+                write!(f, "unknown field `{}` for collection `{}`", field, collection)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FilterError {}
+
+/// Compiles [`Predicate`] trees for one collection, validating field names
+/// against that collection's known schema.
+pub struct FilterBuilder {
+    collection: String,
+    fields: &'static [&'static str],
+}
+
+impl FilterBuilder {
+    /// Build a validator for `collection`, or `None` if the collection is unknown.
+    pub fn for_collection(collection: &str) -> Option<Self> {
+        let fields = match collection {
+            "novels" => NOVEL_FIELDS,
+            "chapters" => CHAPTER_FIELDS,
+            "characters" => CHARACTER_FIELDS,
+            "qa" => QA_FIELDS,
+            _ => return None,
+        };
+        Some(Self {
+            collection: collection.to_string(),
+            fields,
+        })
+    }
+
+    /// Compile a predicate into a Mongo filter document, rejecting any predicate
+    /// that names a field outside the collection schema.
+    pub fn compile(&self, predicate: &Predicate) -> Result<Document, FilterError> {
+        match predicate {
+            Predicate::Eq(field, value) => {
+                self.check(field)?;
+                Ok(doc! { field: value.to_bson(field) })
+            }
+            Predicate::In(field, values) => {
+                self.check(field)?;
+                let array: Vec<Bson> = values.iter().map(|v| v.to_bson(field)).collect();
+                Ok(doc! { field: { "$in": array } })
+            }
+            Predicate::Regex(field, pattern) => {
+                self.check(field)?;
+                Ok(doc! { field: { "$regex": pattern, "$options": "i" } })
+            }
+            Predicate::Range { field, gt, gte, lt, lte } => {
+                self.check(field)?;
+                let mut range = Document::new();
+                if let Some(v) = gt {
+                    range.insert("$gt", v.to_bson(field));
+                }
+                if let Some(v) = gte {
+                    range.insert("$gte", v.to_bson(field));
+                }
+                if let Some(v) = lt {
+                    range.insert("$lt", v.to_bson(field));
+                }
+                if let Some(v) = lte {
+                    range.insert("$lte", v.to_bson(field));
+                }
+                Ok(doc! { field: range })
+            }
+            Predicate::Exists(field, exists) => {
+                self.check(field)?;
+                Ok(doc! { field: { "$exists": exists } })
+            }
+            Predicate::And(parts) => {
+                let compiled = self.compile_all(parts)?;
+                Ok(doc! { "$and": compiled })
+            }
+            Predicate::Or(parts) => {
+                let compiled = self.compile_all(parts)?;
+                Ok(doc! { "$or": compiled })
+            }
+            Predicate::Not(inner) => {
+                let compiled = self.compile(inner)?;
+                Ok(doc! { "$nor": vec![compiled] })
+            }
+        }
+    }
+
+    fn compile_all(&self, parts: &[Predicate]) -> Result<Vec<Document>, FilterError> {
+        parts.iter().map(|p| self.compile(p)).collect()
+    }
+
+    fn check(&self, field: &str) -> Result<(), FilterError> {
+        if self.fields.contains(&field) {
+            Ok(())
+        } else {
+            Err(FilterError::UnknownField {
+                collection: self.collection.clone(),
+                field: field.to_string(),
+            })
+        }
+    }
+}
+
+/// Field-name aliases accepted in natural-language queries.
+fn normalize_field(field: &str) -> String {
+    match field {
+        "chapter_number" => "number".to_string(),
+        "year" => "publication_date".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Recognize comparison clauses in a natural-language query and build the
+/// corresponding predicates. Supported forms: `field = value`, `field > value`
+/// (and `<`, `>=`, `<=`), `field in a,b,c`, and `field between lo and hi`.
+/// Recognized clauses are combined with [`Predicate::And`].
+pub fn parse_predicates(query: &str) -> Option<Predicate> {
+    let lower = query.to_lowercase();
+    let mut predicates = Vec::new();
+
+    // `field between lo and hi` -> inclusive range.
+    let between = regex_captures(&lower, r"(\w+)\s+between\s+(\S+)\s+and\s+(\S+)");
+    for caps in &between {
+        predicates.push(Predicate::Range {
+            field: normalize_field(&caps[0]),
+            gt: None,
+            gte: Some(Scalar::parse(&caps[1])),
+            lt: None,
+            lte: Some(Scalar::parse(&caps[2])),
+        });
+    }
+
+    // `field in a,b,c` -> membership.
+    for caps in &regex_captures(&lower, r"(\w+)\s+in\s+([\w,]+)") {
+        let values = caps[1]
+            .split(',')
+            .map(|v| Scalar::parse(v.trim()))
+            .filter(|v| !matches!(v, Scalar::Str(s) if s.is_empty()))
+            .collect::<Vec<_>>();
+        if !values.is_empty() {
+            predicates.push(Predicate::In(normalize_field(&caps[0]), values));
+        }
+    }
+
+    // `field <op> value` for the comparison and equality operators.
+    for caps in &regex_captures(&lower, r"(\w+)\s*(>=|<=|>|<|=)\s*(\S+)") {
+        let field = normalize_field(&caps[0]);
+        let value = Scalar::parse(&caps[2]);
+        let predicate = match caps[1].as_str() {
+            ">" => Predicate::Range { field, gt: Some(value), gte: None, lt: None, lte: None },
+            ">=" => Predicate::Range { field, gt: None, gte: Some(value), lt: None, lte: None },
+            "<" => Predicate::Range { field, gt: None, gte: None, lt: Some(value), lte: None },
+            "<=" => Predicate::Range { field, gt: None, gte: None, lt: None, lte: Some(value) },
+            _ => Predicate::Eq(field, value),
+        };
+        predicates.push(predicate);
+    }
+
+    match predicates.len() {
+        0 => None,
+        1 => predicates.pop(),
+        _ => Some(Predicate::And(predicates)),
+    }
+}
+
+/// Run `pattern` over `text`, returning the capture groups (group 0 excluded) of
+/// every match. Returns an empty vector if the pattern fails to compile.
+fn regex_captures(text: &str, pattern: &str) -> Vec<Vec<String>> {
+    let regex = match regex::Regex::new(pattern) {
+        Ok(regex) => regex,
+        Err(_) => return Vec::new(),
+    };
+    regex
+        .captures_iter(text)
+        .map(|caps| {
+            caps.iter()
+                .skip(1)
+                .map(|group| group.map(|m| m.as_str().to_string()).unwrap_or_default())
+                .collect()
+        })
+        .collect()
+}
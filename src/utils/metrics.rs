@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+/// Process-wide metrics registry. Tool handlers and the cache record into the
+/// single instance returned by [`metrics`]; the admin `/metrics` endpoint
+/// renders it in Prometheus text format.
+#[derive(Default)]
+pub struct Metrics {
+    tool_calls: Mutex<HashMap<String, u64>>,
+    tool_errors: Mutex<HashMap<String, u64>>,
+    cache_hits: AtomicU64,
+    cache_misses: AtomicU64,
+    db_latency_ms_sum: AtomicU64,
+    db_latency_count: AtomicU64,
+}
+
+impl Metrics {
+    /// Record one invocation of `tool`.
+    pub fn record_tool_call(&self, tool: &str) {
+        *self.tool_calls.lock().unwrap().entry(tool.to_string()).or_insert(0) += 1;
+    }
+
+    /// Record that `tool` returned an error to its caller.
+    pub fn record_tool_error(&self, tool: &str) {
+        *self.tool_errors.lock().unwrap().entry(tool.to_string()).or_insert(0) += 1;
+    }
+
+    /// Record a cache hit.
+    pub fn record_cache_hit(&self) {
+        self.cache_hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a cache miss.
+    pub fn record_cache_miss(&self) {
+        self.cache_misses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record the wall-clock latency of a database search.
+    pub fn record_db_latency(&self, elapsed: Duration) {
+        self.db_latency_ms_sum
+            .fetch_add(elapsed.as_millis() as u64, Ordering::Relaxed);
+        self.db_latency_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Render the collected counters in Prometheus text exposition format.
+    pub fn render_prometheus(&self) -> String {
+        let mut out = String::new();
+
+        out.push_str("# HELP mcp_tool_calls_total Number of tool invocations.\n");
+        out.push_str("# TYPE mcp_tool_calls_total counter\n");
+        for (tool, count) in self.tool_calls.lock().unwrap().iter() {
+            out.push_str(&format!("mcp_tool_calls_total{{tool=\"{}\"}} {}\n", tool, count));
+        }
+
+        out.push_str("# HELP mcp_tool_errors_total Number of tool invocations that errored.\n");
+        out.push_str("# TYPE mcp_tool_errors_total counter\n");
+        for (tool, count) in self.tool_errors.lock().unwrap().iter() {
+            out.push_str(&format!("mcp_tool_errors_total{{tool=\"{}\"}} {}\n", tool, count));
+        }
+
+        out.push_str("# HELP mcp_cache_hits_total Formatted-result cache hits.\n");
+        out.push_str("# TYPE mcp_cache_hits_total counter\n");
+        out.push_str(&format!("mcp_cache_hits_total {}\n", self.cache_hits.load(Ordering::Relaxed)));
+
+        out.push_str("# HELP mcp_cache_misses_total Formatted-result cache misses.\n");
+        out.push_str("# TYPE mcp_cache_misses_total counter\n");
+        out.push_str(&format!(
+            "mcp_cache_misses_total {}\n",
+            self.cache_misses.load(Ordering::Relaxed)
+        ));
+
+        out.push_str("# HELP mcp_db_latency_ms Database search latency in milliseconds.\n");
+        out.push_str("# TYPE mcp_db_latency_ms summary\n");
+        out.push_str(&format!(
+            "mcp_db_latency_ms_sum {}\n",
+            self.db_latency_ms_sum.load(Ordering::Relaxed)
+        ));
+        out.push_str(&format!(
+            "mcp_db_latency_ms_count {}\n",
+            self.db_latency_count.load(Ordering::Relaxed)
+        ));
+
+        out
+    }
+}
+
+/// Access the process-wide [`Metrics`] instance, initializing it on first use.
+pub fn metrics() -> &'static Metrics {
+    static METRICS: OnceLock<Metrics> = OnceLock::new();
+    METRICS.get_or_init(Metrics::default)
+}
@@ -1,24 +1,211 @@
+pub mod filter_builder;
+pub mod metrics;
 pub mod query_parser;
+pub mod query_tree;
 pub use query_parser::QueryParser;
 
 use std::collections::HashMap;
 
-/// Validates the provided authentication token.
-/// Returns true if the token is valid, false otherwise.
-pub fn validate_auth_token(options: &HashMap<String, serde_json::Value>) -> bool {
-    if let Some(token) = options.get("auth_token").and_then(|v| v.as_str()) {
-        // Replace this with actual token validation logic, such as checking against a database or environment variable.
-        const TRUSTED_TOKENS: [&str; 1] = ["trusted_llm_token"];
-        TRUSTED_TOKENS.contains(&token)
-    } else {
-        false
+use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
+use serde::{Deserialize, Serialize};
+
+/// Environment flag gating JWT validation. When unset (or not `1`/`true`/`yes`) the
+/// server runs open for local development and [`validate_auth_token`] accepts
+/// every request without inspecting the token.
+const ENABLE_AUTH_ENV: &str = "MCP_ENABLE_AUTH";
+/// Selects the signature algorithm: `HS256` (default) reads [`JWT_SECRET_ENV`];
+/// `RS256` reads the PEM public key from [`JWT_PUBKEY_ENV`].
+const JWT_ALG_ENV: &str = "MCP_JWT_ALG";
+const JWT_SECRET_ENV: &str = "MCP_JWT_SECRET";
+const JWT_PUBKEY_ENV: &str = "MCP_JWT_PUBKEY";
+
+/// Claims inspected on an incoming MCP JWT. `aud`/`groups` are optional and may
+/// arrive as a single string or an array; [`StringOrVec`] normalises both.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpClaims {
+    pub sub: String,
+    pub exp: i64,
+    #[serde(default)]
+    pub nbf: Option<i64>,
+    #[serde(default)]
+    pub aud: Option<StringOrVec>,
+    #[serde(default)]
+    pub groups: StringOrVec,
+}
+
+/// A JSON field that may be a single string or a list of strings, flattened to
+/// a `Vec` regardless of which shape the issuer used.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum StringOrVec {
+    #[default]
+    None,
+    One(String),
+    Many(Vec<String>),
+}
+
+impl StringOrVec {
+    fn as_slice(&self) -> Vec<&str> {
+        match self {
+            StringOrVec::None => Vec::new(),
+            StringOrVec::One(s) => vec![s.as_str()],
+            StringOrVec::Many(v) => v.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+/// The identities and groups permitted to call the server. Access is granted if
+/// the token's `sub` is in `identities` **or** any of its `groups` is in
+/// `groups`.
+#[derive(Debug, Clone, Default)]
+pub struct AllowedPrincipals {
+    pub identities: Vec<String>,
+    pub groups: Vec<String>,
+}
+
+/// A successfully authenticated caller, handed to downstream handlers for
+/// per-novel authorization instead of a bare `bool`.
+#[derive(Debug, Clone)]
+pub struct ResolvedIdentity {
+    pub subject: String,
+    pub groups: Vec<String>,
+}
+
+/// Why a token was rejected, distinguished so clients can tell what failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthError {
+    Missing,
+    Malformed,
+    Expired,
+    NotYetValid,
+    WrongAudience,
+    UnknownPrincipal,
+    Misconfigured,
+}
+
+impl AuthError {
+    /// Human-readable, claim-specific message paired with [`ERROR_UNAUTHORIZED`].
+    pub fn message(&self) -> &'static str {
+        match self {
+            AuthError::Missing => "Missing authentication token",
+            AuthError::Malformed => "Malformed or unverifiable authentication token",
+            AuthError::Expired => "Authentication token has expired",
+            AuthError::NotYetValid => "Authentication token is not yet valid",
+            AuthError::WrongAudience => "Authentication token audience is not allowed",
+            AuthError::UnknownPrincipal => "Authentication token principal is not permitted",
+            AuthError::Misconfigured => "Server authentication is misconfigured",
+        }
     }
 }
 
+/// Validate the request's `auth_token`, returning the [`ResolvedIdentity`] on
+/// success. When [`ENABLE_AUTH_ENV`] is off the check is skipped and an
+/// anonymous identity is returned, keeping local development friction-free.
+pub fn validate_auth_token(
+    options: &HashMap<String, serde_json::Value>,
+    allowed_audiences: &[String],
+    principals: &AllowedPrincipals,
+) -> Result<ResolvedIdentity, AuthError> {
+    if !auth_enabled() {
+        return Ok(ResolvedIdentity {
+            subject: "anonymous".to_string(),
+            groups: Vec::new(),
+        });
+    }
+
+    let token = options
+        .get("auth_token")
+        .and_then(|v| v.as_str())
+        .ok_or(AuthError::Missing)?;
+
+    let claims = decode_claims(token)?;
+    validate_claims(&claims, allowed_audiences, principals)
+}
+
+/// Whether JWT validation is enabled via [`ENABLE_AUTH_ENV`].
+fn auth_enabled() -> bool {
+    std::env::var(ENABLE_AUTH_ENV)
+        .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false)
+}
+
+/// Decode and signature-verify `token` with the configured algorithm and key,
+/// checking `exp`/`nbf`. Audience and principal checks happen in
+/// [`validate_claims`] so their failures carry distinct messages.
+fn decode_claims(token: &str) -> Result<McpClaims, AuthError> {
+    let (algorithm, key) = signing_key()?;
+    let mut validation = Validation::new(algorithm);
+    // We validate the audience ourselves in `validate_claims` to report a
+    // precise message, so turn off the library's own audience check.
+    validation.validate_aud = false;
+    match decode::<McpClaims>(token, &key, &validation) {
+        Ok(data) => Ok(data.claims),
+        Err(e) => Err(match e.kind() {
+            jsonwebtoken::errors::ErrorKind::ExpiredSignature => AuthError::Expired,
+            jsonwebtoken::errors::ErrorKind::ImmatureSignature => AuthError::NotYetValid,
+            _ => AuthError::Malformed,
+        }),
+    }
+}
+
+/// Resolve the algorithm and decoding key from the environment.
+fn signing_key() -> Result<(Algorithm, DecodingKey), AuthError> {
+    let alg = std::env::var(JWT_ALG_ENV).unwrap_or_else(|_| "HS256".to_string());
+    match alg.to_ascii_uppercase().as_str() {
+        "HS256" => {
+            let secret = std::env::var(JWT_SECRET_ENV).map_err(|_| AuthError::Misconfigured)?;
+            Ok((Algorithm::HS256, DecodingKey::from_secret(secret.as_bytes())))
+        }
+        "RS256" => {
+            let pem = std::env::var(JWT_PUBKEY_ENV).map_err(|_| AuthError::Misconfigured)?;
+            let key = DecodingKey::from_rsa_pem(pem.as_bytes())
+                .map_err(|_| AuthError::Misconfigured)?;
+            Ok((Algorithm::RS256, key))
+        }
+        _ => Err(AuthError::Misconfigured),
+    }
+}
+
+/// Check the decoded `claims` against the configured audiences and principals,
+/// returning the resolved identity. An empty `allowed_audiences` disables the
+/// audience check; an empty principal set permits any authenticated subject.
+pub fn validate_claims(
+    claims: &McpClaims,
+    allowed_audiences: &[String],
+    principals: &AllowedPrincipals,
+) -> Result<ResolvedIdentity, AuthError> {
+    if !allowed_audiences.is_empty() {
+        let token_aud = claims.aud.as_ref().map(StringOrVec::as_slice).unwrap_or_default();
+        let audience_ok = token_aud
+            .iter()
+            .any(|a| allowed_audiences.iter().any(|allowed| allowed == a));
+        if !audience_ok {
+            return Err(AuthError::WrongAudience);
+        }
+    }
+
+    let groups = claims.groups.as_slice();
+    let principal_configured = !principals.identities.is_empty() || !principals.groups.is_empty();
+    if principal_configured {
+        let identity_ok = principals.identities.iter().any(|id| id == &claims.sub);
+        let group_ok = groups
+            .iter()
+            .any(|g| principals.groups.iter().any(|allowed| allowed == g));
+        if !identity_ok && !group_ok {
+            return Err(AuthError::UnknownPrincipal);
+        }
+    }
+
+    Ok(ResolvedIdentity {
+        subject: claims.sub.clone(),
+        groups: groups.into_iter().map(str::to_string).collect(),
+    })
+}
+
 /// Error code for unauthorized access
 pub const ERROR_UNAUTHORIZED: i32 = -32604;
 
 /// Generates an error message for unauthorized access.
 pub fn unauthorized_error_message() -> String {
     "Invalid or missing authentication token".to_string()
-}
\ No newline at end of file
+}
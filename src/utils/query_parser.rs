@@ -1,4 +1,7 @@
 use crate::models::{SearchFilters, SearchParams};
+use crate::utils::filter_builder::{self, FilterBuilder, FilterError};
+use crate::utils::query_tree::{self, Operation};
+use mongodb::bson::Document;
 use regex::Regex;
 use std::collections::{HashSet, HashMap};
 use serde_json::Value;
@@ -28,9 +31,63 @@ impl QueryParser {
             keywords,
             filters: Some(filters),
             limit,
+            page_token: None,
+            filter_expr: None,
+            compiled_filter: None,
+            text_query: Some(query.to_string()),
         }
     }
     
+    /// Expand a keyword list with the synonyms known to `synonyms`, so a query
+    /// using a nickname also matches the canonical name (and vice versa). Each
+    /// term contributes itself plus its linked variants; the result is
+    /// deduplicated while preserving order. Because the search path ORs the
+    /// keywords through `$text`, the expanded set needs no further merge step.
+    pub fn expand_synonyms(
+        keywords: &[String],
+        synonyms: &crate::services::synonyms::Synonyms,
+    ) -> Vec<String> {
+        let mut expanded: Vec<String> = Vec::new();
+        for keyword in keywords {
+            for variant in synonyms.expand(keyword) {
+                if !expanded.contains(&variant) {
+                    expanded.push(variant);
+                }
+            }
+        }
+        expanded
+    }
+
+    /// Parse a query into a typo-tolerant boolean [`Operation`] tree shaped by
+    /// parentheses and the `AND`/`OR` keywords. See [`query_tree`] for how the
+    /// tree compiles to a Mongo filter.
+    pub fn build_query_tree(query: &str) -> Operation {
+        query_tree::build_query_tree(query)
+    }
+
+    /// Whether a parsed query type asks for meaning-based retrieval, in which
+    /// case it should be routed to
+    /// [`VectorSearchService`](crate::services::vector_search::VectorSearchService)
+    /// rather than the literal `$text`/regex search paths.
+    pub fn wants_semantic_search(query_type: &str) -> bool {
+        query_type == "similar"
+    }
+
+    /// Parse the comparison clauses of a query into a validated Mongo filter for
+    /// `collection`, alongside the flat [`SearchFilters`] regex extraction.
+    /// Returns `Ok(None)` when the query carries no structured predicates, and a
+    /// [`FilterError`] when it references a field outside the collection schema.
+    pub fn build_filter(collection: &str, query: &str) -> Result<Option<Document>, FilterError> {
+        let builder = match FilterBuilder::for_collection(collection) {
+            Some(builder) => builder,
+            None => return Ok(None),
+        };
+        match filter_builder::parse_predicates(query) {
+            Some(predicate) => builder.compile(&predicate).map(Some),
+            None => Ok(None),
+        }
+    }
+
     fn extract_collection(query: &str) -> Option<String> {
         let collections = [
             ("novel", "novels"),
@@ -60,6 +117,9 @@ impl QueryParser {
             ("find", "search"),
             ("list", "list"),
             ("all", "list"),
+            ("similar", "similar"),
+            ("like", "similar"),
+            ("related", "similar"),
         ];
         
         for (keyword, query_type) in query_types.iter() {
@@ -91,11 +151,7 @@ impl QueryParser {
     }
     
     fn extract_filters(query: &str) -> SearchFilters {
-        let mut filters = SearchFilters {
-            novel_id: None,
-            character_name: None,
-            tags: None,
-        };
+        let mut filters = SearchFilters::default();
         
         // Extract novel ID or name
         if let Some(novel_id) = Self::extract_novel_id(query) {
@@ -0,0 +1,376 @@
+//! Typo-tolerant boolean query parsing.
+//!
+//! [`build_query_tree`] turns a natural-language search string into an
+//! [`Operation`] tree shaped by parentheses and the `AND`/`OR` keywords. Each
+//! leaf carries a [`LeafKind`] describing how its term should match: an exact
+//! phrase, a fuzzy (Levenshtein-bounded) term, or a prefix for incremental
+//! typing. [`Operation::compile`] lowers the tree onto a Mongo `$and`/`$or` of
+//! `$regex`/`$in` clauses, expanding tolerant leaves against the collection's
+//! distinct indexed terms via a small Levenshtein automaton.
+
+use mongodb::bson::{doc, Bson, Document};
+
+/// How a leaf term is matched against the indexed field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LeafKind {
+    /// Match the whole term exactly (anchored, case-insensitive).
+    Exact,
+    /// Match any indexed term within the term's allowed edit distance.
+    Tolerant,
+    /// Match any indexed value that starts with the term.
+    Prefix,
+}
+
+/// A single search term plus the way it should be matched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query {
+    pub term: String,
+    pub kind: LeafKind,
+}
+
+/// A boolean combination of [`Query`] leaves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operation {
+    And(Vec<Operation>),
+    Or(Vec<Operation>),
+    Query(Query),
+}
+
+/// The edit distance tolerated for a term of the given character length:
+/// 0 for short terms, scaling to 2 for long ones so corrections stay plausible.
+pub fn allowed_edits(len: usize) -> usize {
+    match len {
+        0..=4 => 0,
+        5..=8 => 1,
+        _ => 2,
+    }
+}
+
+/// A Levenshtein automaton expressed as an NFA over `term`, with states
+/// `(position, edits)` and transitions for match, substitute, insert, and
+/// delete. [`accepts`](Self::accepts) reports whether a candidate word is
+/// within `max_edits` of the term.
+pub struct LevenshteinNfa {
+    term: Vec<char>,
+    max_edits: usize,
+}
+
+impl LevenshteinNfa {
+    pub fn new(term: &str, max_edits: usize) -> Self {
+        Self {
+            term: term.chars().collect(),
+            max_edits,
+        }
+    }
+
+    /// Expand the current states with epsilon (deletion) transitions, which
+    /// advance the term position while spending an edit without consuming input.
+    fn closure(&self, states: &mut Vec<(usize, usize)>) {
+        let mut i = 0;
+        while i < states.len() {
+            let (pos, edits) = states[i];
+            if pos < self.term.len() && edits < self.max_edits {
+                let next = (pos + 1, edits + 1);
+                if !states.contains(&next) {
+                    states.push(next);
+                }
+            }
+            i += 1;
+        }
+    }
+
+    /// Whether `candidate` is within `max_edits` of the term.
+    pub fn accepts(&self, candidate: &str) -> bool {
+        let mut states = vec![(0usize, 0usize)];
+        self.closure(&mut states);
+
+        for c in candidate.chars() {
+            let mut next: Vec<(usize, usize)> = Vec::new();
+            let mut push = |state: (usize, usize)| {
+                if !next.contains(&state) {
+                    next.push(state);
+                }
+            };
+            for &(pos, edits) in &states {
+                // Insertion: consume the candidate char without advancing the term.
+                if edits < self.max_edits {
+                    push((pos, edits + 1));
+                }
+                if pos < self.term.len() {
+                    if self.term[pos] == c {
+                        push((pos + 1, edits)); // match
+                    } else if edits < self.max_edits {
+                        push((pos + 1, edits + 1)); // substitute
+                    }
+                }
+            }
+            self.closure(&mut next);
+            if next.is_empty() {
+                return false;
+            }
+            states = next;
+        }
+
+        states
+            .iter()
+            .any(|&(pos, edits)| pos == self.term.len() && edits <= self.max_edits)
+    }
+}
+
+impl Query {
+    /// Lower this leaf onto a Mongo predicate over `field`. Tolerant leaves are
+    /// expanded against `dictionary` (the distinct indexed terms for the target
+    /// collection); an empty expansion falls back to the literal term so the
+    /// clause still matches rather than silently matching nothing.
+    fn compile(&self, field: &str, dictionary: &[String]) -> Document {
+        match self.kind {
+            LeafKind::Exact => doc! {
+                field: { "$regex": format!("^{}$", regex::escape(&self.term)), "$options": "i" }
+            },
+            LeafKind::Prefix => doc! {
+                field: { "$regex": format!("^{}", regex::escape(&self.term)), "$options": "i" }
+            },
+            LeafKind::Tolerant => {
+                let lowered = self.term.to_lowercase();
+                let nfa = LevenshteinNfa::new(&lowered, allowed_edits(lowered.chars().count()));
+                let mut matches: Vec<Bson> = dictionary
+                    .iter()
+                    .filter(|word| nfa.accepts(&word.to_lowercase()))
+                    .map(|word| Bson::String(word.clone()))
+                    .collect();
+                if matches.is_empty() {
+                    matches.push(Bson::String(self.term.clone()));
+                }
+                doc! { field: { "$in": matches } }
+            }
+        }
+    }
+}
+
+impl Operation {
+    /// Lower the whole tree onto a single Mongo filter document.
+    pub fn compile(&self, field: &str, dictionary: &[String]) -> Document {
+        match self {
+            Operation::And(ops) => {
+                let clauses: Vec<Bson> = ops
+                    .iter()
+                    .map(|op| Bson::Document(op.compile(field, dictionary)))
+                    .collect();
+                doc! { "$and": clauses }
+            }
+            Operation::Or(ops) => {
+                let clauses: Vec<Bson> = ops
+                    .iter()
+                    .map(|op| Bson::Document(op.compile(field, dictionary)))
+                    .collect();
+                doc! { "$or": clauses }
+            }
+            Operation::Query(query) => query.compile(field, dictionary),
+        }
+    }
+}
+
+/// A lexical token in a boolean query.
+enum Token {
+    And,
+    Or,
+    Open,
+    Close,
+    Word(String),
+}
+
+fn tokenize(query: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+
+    let flush = |word: &mut String, tokens: &mut Vec<Token>| {
+        if !word.is_empty() {
+            match word.as_str() {
+                "AND" => tokens.push(Token::And),
+                "OR" => tokens.push(Token::Or),
+                _ => tokens.push(Token::Word(std::mem::take(word))),
+            }
+            word.clear();
+        }
+    };
+
+    for c in query.chars() {
+        match c {
+            '(' => {
+                flush(&mut word, &mut tokens);
+                tokens.push(Token::Open);
+            }
+            ')' => {
+                flush(&mut word, &mut tokens);
+                tokens.push(Token::Close);
+            }
+            c if c.is_whitespace() => flush(&mut word, &mut tokens),
+            c => word.push(c),
+        }
+    }
+    flush(&mut word, &mut tokens);
+    tokens
+}
+
+/// Recursive-descent parser over [`Token`]s. `OR` binds loosest, then `AND`
+/// (also the implicit operator between adjacent factors), then parenthesized
+/// groups and bare words.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    /// Index of the last word token, which becomes a [`LeafKind::Prefix`] leaf.
+    last_word: Option<usize>,
+}
+
+impl Parser {
+    fn new(tokens: Vec<Token>) -> Self {
+        let last_word = tokens
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, t)| matches!(t, Token::Word(_)))
+            .map(|(i, _)| i);
+        Self { tokens, pos: 0, last_word }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn parse(&mut self) -> Operation {
+        self.parse_or()
+    }
+
+    fn parse_or(&mut self) -> Operation {
+        let mut operands = vec![self.parse_and()];
+        while matches!(self.peek(), Some(Token::Or)) {
+            self.pos += 1;
+            operands.push(self.parse_and());
+        }
+        if operands.len() == 1 {
+            operands.pop().unwrap()
+        } else {
+            Operation::Or(operands)
+        }
+    }
+
+    fn parse_and(&mut self) -> Operation {
+        let mut operands = vec![self.parse_factor()];
+        loop {
+            match self.peek() {
+                Some(Token::And) => {
+                    self.pos += 1;
+                    operands.push(self.parse_factor());
+                }
+                // Implicit AND between adjacent words/groups.
+                Some(Token::Word(_)) | Some(Token::Open) => {
+                    operands.push(self.parse_factor());
+                }
+                _ => break,
+            }
+        }
+        if operands.len() == 1 {
+            operands.pop().unwrap()
+        } else {
+            Operation::And(operands)
+        }
+    }
+
+    fn parse_factor(&mut self) -> Operation {
+        match self.peek() {
+            Some(Token::Open) => {
+                self.pos += 1;
+                let inner = self.parse_or();
+                if matches!(self.peek(), Some(Token::Close)) {
+                    self.pos += 1;
+                }
+                inner
+            }
+            Some(Token::Word(_)) => {
+                let index = self.pos;
+                let word = match &self.tokens[index] {
+                    Token::Word(w) => w.clone(),
+                    _ => unreachable!(),
+                };
+                self.pos += 1;
+                Operation::Query(self.leaf(index, word))
+            }
+            // A stray operator or close paren: treat as an empty exact match so
+            // the tree stays well-formed.
+            _ => {
+                self.pos += 1;
+                Operation::Query(Query { term: String::new(), kind: LeafKind::Exact })
+            }
+        }
+    }
+
+    /// Classify a word leaf: a quoted term is [`LeafKind::Exact`], the final
+    /// bare word is [`LeafKind::Prefix`] so incremental typing matches, and
+    /// everything else is [`LeafKind::Tolerant`].
+    fn leaf(&self, index: usize, word: String) -> Query {
+        if word.len() >= 2 && word.starts_with('"') && word.ends_with('"') {
+            return Query {
+                term: word.trim_matches('"').to_string(),
+                kind: LeafKind::Exact,
+            };
+        }
+        let kind = if Some(index) == self.last_word {
+            LeafKind::Prefix
+        } else {
+            LeafKind::Tolerant
+        };
+        Query { term: word, kind }
+    }
+}
+
+/// Parse a query string into an [`Operation`] tree.
+pub fn build_query_tree(query: &str) -> Operation {
+    Parser::new(tokenize(query)).parse()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allowed_edits_scales_with_term_length() {
+        assert_eq!(allowed_edits(3), 0);
+        assert_eq!(allowed_edits(6), 1);
+        assert_eq!(allowed_edits(12), 2);
+    }
+
+    #[test]
+    fn levenshtein_nfa_accepts_within_budget_and_rejects_beyond_it() {
+        let nfa = LevenshteinNfa::new("dragon", 1);
+        assert!(nfa.accepts("dragon"));
+        assert!(nfa.accepts("dragn"));
+        assert!(!nfa.accepts("elephant"));
+    }
+
+    #[test]
+    fn single_word_query_is_a_prefix_leaf() {
+        let op = build_query_tree("dragon");
+        assert_eq!(
+            op,
+            Operation::Query(Query { term: "dragon".to_string(), kind: LeafKind::Prefix })
+        );
+    }
+
+    #[test]
+    fn quoted_word_is_an_exact_leaf() {
+        let op = build_query_tree("\"dragon\"");
+        assert_eq!(
+            op,
+            Operation::Query(Query { term: "dragon".to_string(), kind: LeafKind::Exact })
+        );
+    }
+
+    #[test]
+    fn and_or_keywords_build_the_matching_boolean_tree() {
+        let and_op = build_query_tree("dragon AND sword");
+        assert!(matches!(and_op, Operation::And(ref ops) if ops.len() == 2));
+
+        let or_op = build_query_tree("dragon OR sword");
+        assert!(matches!(or_op, Operation::Or(ref ops) if ops.len() == 2));
+    }
+}